@@ -0,0 +1,373 @@
+use std::io::Seek;
+use futures_io::{ AsyncRead, AsyncSeek };
+use crate::{ utils::{ AsyncLoader, Sparse }, metadata::{ Bext, FrameRate, Timecode, Info, Id3Tag, Cues, Sampler, Instrument, Acid, Ixml, PeakEnvelope, Cart, Peak }, common::{ FileFormat, ChunkPosition, Chunk, ReadOptions, Diagnostic, decode, RIFF_HEADER_SIZE }, reader::Reader };
+
+const READ_AHEAD: usize = 4096;
+
+pub struct AsyncReader< F: AsyncRead + AsyncSeek + Unpin > {
+    loader: AsyncLoader< F >,
+    header: Reader< Sparse >,
+    buffer: Vec< u8 >
+}
+
+impl< F: AsyncRead + AsyncSeek + Unpin > AsyncReader< F > {
+    pub async fn from(from: F) -> Option< AsyncReader< F > > {
+        Self::from_with(from, ReadOptions::new()).await
+    }
+
+    pub async fn from_with(from: F, options: ReadOptions) -> Option< AsyncReader< F > > {
+        let mut loader = AsyncLoader::from(from);
+        let mut image  = Self::scan(&mut loader).await?;
+
+        // The walk covers what the reader parses in the usual layouts; anything else it turns out to need
+        // (e.g. when recovering from a broken one) is fetched before it runs again.
+        loop {
+            image.rewind().ok()?;
+            let found = Reader::from_with(&mut image, options).is_some();
+
+            match image.take_missing() {
+                Some((offset, n)) => { Self::fetch(&mut loader, &mut image, offset, n.max(READ_AHEAD)).await?; },
+                None if found     => break,
+                None              => return None
+            }
+        }
+
+        image.rewind().ok()?;
+        let header = Reader::from_with(image, options)?;
+        Some(AsyncReader { loader, header, buffer: Vec::new() })
+    }
+
+    async fn fetch(loader: &mut AsyncLoader< F >, image: &mut Sparse, offset: usize, n: usize) -> Option< Vec< u8 > > {
+        let mut data = vec![0u8; n.min(image.len().saturating_sub(offset))];
+        loader.seek(offset).await?;
+        loader.load(data.as_mut_slice()).await?;
+        image.insert(offset, data.clone());
+        Some(data)
+    }
+
+    // Walks the chunk headers once, fetching only the payloads the reader parses while opening a file.
+    async fn scan(loader: &mut AsyncLoader< F >) -> Option< Sparse > {
+        let len       = loader.len().await?;
+        let mut image = Sparse::new(len);
+        let mut pos   = RIFF_HEADER_SIZE;
+        let mut ds64  = None;
+
+        Self::fetch(loader, &mut image, 0, RIFF_HEADER_SIZE).await?;
+
+        while pos + 8 <= len {
+            let (id, size) = Self::fetch_header(loader, &mut image, pos).await?;
+            let size       = match (&id, ds64) {
+                (b"data", Some(data_size)) if size == 0xFFFFFFFF => data_size,
+                (b"data", _              ) if size == 0xFFFFFFFF => break,
+                _                                                => size as usize
+            };
+
+            match &id {
+                b"ds64" => {
+                    let data = Self::fetch(loader, &mut image, pos + 8, size).await?;
+                    ds64     = data.get(8..16).and_then(|bytes| bytes.try_into().ok()).map(|bytes| u64::from_le_bytes(bytes) as usize);
+                },
+                b"fmt " | b"fact" => {
+                    Self::fetch(loader, &mut image, pos + 8, size).await?;
+                },
+                b"LIST" if size >= 4 && Self::fetch(loader, &mut image, pos + 8, 4).await? == b"wavl" => {
+                    Self::scan_wavl(loader, &mut image, pos + 12, (pos + 8).saturating_add(size)).await?;
+                },
+                _ => {}
+            }
+
+            match (pos + 8).checked_add(size) {
+                Some(end) => pos = end + size % 2,
+                None      => break
+            }
+        }
+
+        Some(image)
+    }
+
+    async fn scan_wavl(loader: &mut AsyncLoader< F >, image: &mut Sparse, mut pos: usize, end: usize) -> Option< () > {
+        let end = end.min(image.len());
+
+        while pos + 8 <= end {
+            let (id, size) = Self::fetch_header(loader, image, pos).await?;
+
+            if &id == b"slnt" {
+                Self::fetch(loader, image, pos + 8, 4).await?;
+            }
+
+            pos = pos + 8 + size as usize + size as usize % 2;
+        }
+
+        Some(())
+    }
+
+    async fn fetch_header(loader: &mut AsyncLoader< F >, image: &mut Sparse, pos: usize) -> Option< ([u8; 4], u32) > {
+        let header = Self::fetch(loader, image, pos, 8).await?;
+        Some(([header[ 0 ], header[ 1 ], header[ 2 ], header[ 3 ]], u32::from_le_bytes([header[ 4 ], header[ 5 ], header[ 6 ], header[ 7 ]])))
+    }
+
+    // Metadata payloads stay on the other side until something asks for them.
+    async fn fetch_chunks(&mut self, ids: &[[u8; 4]]) -> Option< () > {
+        let chunks = self.header.chunks().iter().filter(|chunk| chunk.position() != ChunkPosition::Data && ids.contains(&chunk.id())).copied().collect::< Vec< Chunk > >();
+
+        for chunk in chunks {
+            if !self.header.get_mut().contains(chunk.data_offset(), chunk.size()) {
+                Self::fetch(&mut self.loader, self.header.get_mut(), chunk.data_offset(), chunk.size()).await?;
+            }
+        }
+
+        Some(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.header.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.header.is_empty()
+    }
+
+    pub fn format(&self) -> FileFormat {
+        self.header.format()
+    }
+
+    pub fn warnings(&self) -> &[Diagnostic] {
+        self.header.warnings()
+    }
+
+    pub fn chunks(&self) -> &[Chunk] {
+        self.header.chunks()
+    }
+
+    pub fn find_chunk(&self, id: &[u8; 4]) -> Option< Chunk > {
+        self.header.find_chunk(id)
+    }
+
+    pub async fn read_chunk(&mut self, chunk: &Chunk) -> Option< Vec< u8 > > {
+        let mut ret = vec![0u8; chunk.size()];
+        self.loader.seek(chunk.data_offset()).await?;
+        self.loader.load(ret.as_mut_slice()).await?;
+        Some(ret)
+    }
+
+    pub async fn bext(&mut self) -> Option< Bext > {
+        self.fetch_chunks(&[*b"bext"]).await?;
+        self.header.bext()
+    }
+
+    pub async fn info(&mut self) -> Option< Info > {
+        self.fetch_chunks(&[*b"LIST"]).await?;
+        self.header.info()
+    }
+
+    pub async fn id3(&mut self) -> Option< Id3Tag > {
+        self.fetch_chunks(&[*b"id3 ", *b"ID3 "]).await?;
+        self.header.id3()
+    }
+
+    pub async fn cues(&mut self) -> Option< Cues > {
+        self.fetch_chunks(&[*b"cue ", *b"LIST"]).await?;
+        self.header.cues()
+    }
+
+    pub async fn sampler(&mut self) -> Option< Sampler > {
+        self.fetch_chunks(&[*b"smpl"]).await?;
+        self.header.sampler()
+    }
+
+    pub async fn instrument(&mut self) -> Option< Instrument > {
+        self.fetch_chunks(&[*b"inst"]).await?;
+        self.header.instrument()
+    }
+
+    pub async fn acid(&mut self) -> Option< Acid > {
+        self.fetch_chunks(&[*b"acid"]).await?;
+        self.header.acid()
+    }
+
+    pub async fn ixml_raw(&mut self) -> Option< String > {
+        self.fetch_chunks(&[*b"iXML"]).await?;
+        self.header.ixml_raw()
+    }
+
+    pub async fn ixml(&mut self) -> Option< Ixml > {
+        self.fetch_chunks(&[*b"iXML"]).await?;
+        self.header.ixml()
+    }
+
+    pub async fn peak_envelope(&mut self) -> Option< PeakEnvelope > {
+        self.fetch_chunks(&[*b"levl"]).await?;
+        self.header.peak_envelope()
+    }
+
+    pub async fn xmp(&mut self) -> Option< String > {
+        self.fetch_chunks(&[*b"_PMX"]).await?;
+        self.header.xmp()
+    }
+
+    pub async fn peak(&mut self) -> Option< Peak > {
+        self.fetch_chunks(&[*b"PEAK"]).await?;
+        self.header.peak()
+    }
+
+    pub async fn cart(&mut self) -> Option< Cart > {
+        self.fetch_chunks(&[*b"cart"]).await?;
+        self.header.cart()
+    }
+
+    pub async fn start_timecode(&mut self, rate: FrameRate) -> Option< Timecode > {
+        self.fetch_chunks(&[*b"bext"]).await?;
+        self.header.start_timecode(rate)
+    }
+
+    pub async fn timecode_at(&mut self, frame: usize, rate: FrameRate) -> Option< Timecode > {
+        self.fetch_chunks(&[*b"bext"]).await?;
+        self.header.timecode_at(frame, rate)
+    }
+
+    pub fn pos(&mut self) -> usize {
+        self.header.pos()
+    }
+
+    pub fn skip(&mut self, n: usize) -> Option< () > {
+        self.header.skip(n)
+    }
+
+    pub fn rewind(&mut self, n: usize) -> Option< () > {
+        self.header.rewind(n)
+    }
+
+    pub fn seek(&mut self, n: usize) -> Option< () > {
+        self.header.seek(n)
+    }
+
+    pub async fn read(&mut self, to: &mut [f32]) -> Option< () > {
+        let sample   = self.header.format().sample();
+        let depth    = sample.depth() as usize;
+        let mut done = 0;
+
+        for (offset, n) in self.header.runs(to.len())? {
+            let to = &mut to[ done..done + n ];
+
+            match offset {
+                Some(offset) => {
+                    self.buffer.resize(n * depth, 0);
+                    self.loader.seek(offset).await?;
+                    self.loader.load(self.buffer.as_mut_slice()).await?;
+                    decode(sample, &self.buffer, to);
+                },
+                None => {
+                    self.buffer.clear();
+                    self.buffer.resize(n * depth, sample.silence());
+                    decode(sample, &self.buffer, to);
+                }
+            }
+
+            done += n;
+        }
+
+        self.header.skip(done)
+    }
+
+    pub fn into_inner(self) -> F {
+        self.loader.into_inner()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{ io::{ Cursor, SeekFrom }, pin::Pin, task::{ Context, Poll } };
+    use futures_io::{ AsyncRead, AsyncSeek };
+    use crate::{ utils::fixture::{ Memory, block_on, chunk, riff, fmt_pcm16, pcm16 }, common::{ ReadOptions, Issue }, reader::Reader };
+    use super::AsyncReader;
+
+    // Counts the bytes handed out, to tell which ranges were fetched.
+    struct Counted(Memory, usize);
+
+    impl AsyncRead for Counted {
+        fn poll_read(mut self: Pin< &mut Self >, cx: &mut Context, buf: &mut [u8]) -> Poll< std::io::Result< usize > > {
+            let ret = Pin::new(&mut self.0).poll_read(cx, buf);
+
+            if let Poll::Ready(Ok(n)) = ret {
+                self.1 += n;
+            }
+
+            ret
+        }
+    }
+
+    impl AsyncSeek for Counted {
+        fn poll_seek(mut self: Pin< &mut Self >, cx: &mut Context, pos: SeekFrom) -> Poll< std::io::Result< u64 > > {
+            Pin::new(&mut self.0).poll_seek(cx, pos)
+        }
+    }
+
+    fn unpadded() -> Vec< u8 > {
+        let mut ixml = b"iXML".to_vec();
+        ixml.extend_from_slice(&3u32.to_le_bytes());
+        ixml.extend_from_slice(b"<a>");
+        riff(&[fmt_pcm16(1, 8000), ixml, chunk(b"data", &pcm16(&[1, 2]))])
+    }
+
+    #[test]
+    fn matches_reader() {
+        let file       = riff(&[fmt_pcm16(2, 8000), chunk(b"iXML", b"<BWFXML/>"), chunk(b"data", &pcm16(&[1, -1, 2, -2]))]);
+        let mut reader = Reader::from(Cursor::new(file.clone())).unwrap();
+        let mut async_ = block_on(AsyncReader::from(Memory(Cursor::new(file)))).unwrap();
+        assert!(async_.chunks() == reader.chunks());
+        assert_eq!(block_on(async_.ixml_raw()), reader.ixml_raw());
+
+        let mut a = vec![0f32; reader.len()];
+        let mut b = vec![0f32; async_.len()];
+        reader.read(&mut a).unwrap();
+        block_on(async_.read(&mut b)).unwrap();
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn tolerates_missing_pad() {
+        let options = ReadOptions::new().with_tolerate_missing_pad(true);
+        assert!(block_on(AsyncReader::from(Memory(Cursor::new(unpadded())))).is_none());
+
+        let reader = block_on(AsyncReader::from_with(Memory(Cursor::new(unpadded())), options)).unwrap();
+        assert!(reader.warnings().iter().any(|warning| matches!(warning.issue(), Issue::MissingPad { id } if &id == b"iXML")));
+        assert_eq!(reader.len(), 2);
+    }
+
+    #[test]
+    fn rejects_truncated_header() {
+        let file = riff(&[fmt_pcm16(1, 8000)])[ ..20 ].to_vec();
+        assert!(block_on(AsyncReader::from(Memory(Cursor::new(file)))).is_none());
+    }
+    #[test]
+    fn fetches_metadata_on_demand() {
+        let file   = riff(&[fmt_pcm16(1, 8000), chunk(b"iXML", &[b'x'; 4096]), chunk(b"data", &pcm16(&[1, 2]))]);
+        let opened = block_on(AsyncReader::from(Counted(Memory(Cursor::new(file.clone())), 0))).unwrap();
+        assert!(opened.into_inner().1 < 100);
+
+        let mut reader = block_on(AsyncReader::from(Counted(Memory(Cursor::new(file)), 0))).unwrap();
+        assert_eq!(block_on(reader.ixml_raw()).unwrap().len(), 4096);
+        assert_eq!(block_on(reader.ixml_raw()).unwrap().len(), 4096);
+        assert!((4096..4200).contains(&reader.into_inner().1));
+    }
+    #[test]
+    fn reads_wave_list_silence_like_reader() {
+        let mut fmt = fmt_pcm16(1, 8000);
+        fmt[ 16..20 ].copy_from_slice(&8000u32.to_le_bytes());
+        fmt[ 20..22 ].copy_from_slice(&1u16.to_le_bytes());
+        fmt[ 22..24 ].copy_from_slice(&8u16.to_le_bytes());
+
+        let mut wavl = b"wavl".to_vec();
+        wavl.extend(chunk(b"data", &[0xC0]));
+        wavl.extend(chunk(b"slnt", &2u32.to_le_bytes()));
+
+        let file       = riff(&[fmt, chunk(b"LIST", &wavl)]);
+        let mut reader = Reader::from(Cursor::new(file.clone())).unwrap();
+        let mut async_ = block_on(AsyncReader::from(Memory(Cursor::new(file)))).unwrap();
+        let mut a      = [0f32; 3];
+        let mut b      = [0f32; 3];
+        reader.read(&mut a).unwrap();
+        block_on(async_.read(&mut b)).unwrap();
+        assert_eq!(a, b);
+    }
+}
@@ -0,0 +1,216 @@
+use futures_io::{ AsyncWrite, AsyncSeek };
+use crate::{ utils::{ AsyncSaver, Journal }, metadata::{ Bext, Timecode, Info, Id3Tag, Cues, Sampler, Instrument, Acid, Ixml, Cart }, common::{ FileFormat, ChunkPosition }, writer::Writer };
+
+pub struct AsyncWriter< T: AsyncWrite + AsyncSeek + Unpin > {
+    saver  : AsyncSaver< T >  ,
+    writer : Writer< Journal >,
+    pending: Pending
+}
+
+// Drop cannot await, so unlike Writer nothing is finalized implicitly: finalize().await (or
+// into_inner().await) has to follow the last change, and debug builds catch a writer dropped before that.
+struct Pending(bool);
+
+impl Drop for Pending {
+    fn drop(&mut self) {
+        debug_assert!(!self.0 || std::thread::panicking(), "AsyncWriter dropped without finalize().await");
+    }
+}
+
+impl< T: AsyncWrite + AsyncSeek + Unpin > AsyncWriter< T > {
+    pub fn to(to: T, format: FileFormat) -> Option< AsyncWriter< T > > {
+        Some(AsyncWriter { saver: AsyncSaver::to(to), writer: Writer::to(Journal::new(), format)?, pending: Pending(true) })
+    }
+
+    pub fn to_rf64(to: T, format: FileFormat) -> Option< AsyncWriter< T > > {
+        Some(AsyncWriter { saver: AsyncSaver::to(to), writer: Writer::to_rf64(Journal::new(), format)?, pending: Pending(true) })
+    }
+
+    pub fn add_chunk(&mut self, id: &[u8; 4], data: &[u8], position: ChunkPosition) -> Option< () > {
+        self.writer().add_chunk(id, data, position)
+    }
+
+    pub fn add_bext(&mut self, bext: &Bext) -> Option< () > {
+        self.writer().add_bext(bext)
+    }
+
+    pub fn add_info(&mut self, info: &Info) -> Option< () > {
+        self.writer().add_info(info)
+    }
+
+    pub fn add_id3(&mut self, id3: &Id3Tag) -> Option< () > {
+        self.writer().add_id3(id3)
+    }
+
+    pub fn add_cues(&mut self, cues: &Cues) -> Option< () > {
+        self.writer().add_cues(cues)
+    }
+
+    pub fn add_sampler(&mut self, sampler: &Sampler) -> Option< () > {
+        self.writer().add_sampler(sampler)
+    }
+
+    pub fn add_instrument(&mut self, instrument: &Instrument) -> Option< () > {
+        self.writer().add_instrument(instrument)
+    }
+
+    pub fn add_acid(&mut self, acid: &Acid) -> Option< () > {
+        self.writer().add_acid(acid)
+    }
+
+    pub fn add_ixml_raw(&mut self, xml: &str) -> Option< () > {
+        self.writer().add_ixml_raw(xml)
+    }
+
+    pub fn add_ixml(&mut self, ixml: &Ixml) -> Option< () > {
+        self.writer().add_ixml(ixml)
+    }
+
+    pub fn add_xmp(&mut self, xmp: &str) -> Option< () > {
+        self.writer().add_xmp(xmp)
+    }
+
+    pub fn add_cart(&mut self, cart: &Cart) -> Option< () > {
+        self.writer().add_cart(cart)
+    }
+
+    pub fn enable_peak_envelope(&mut self, block_size: usize) -> Option< () > {
+        self.writer().enable_peak_envelope(block_size)
+    }
+
+    pub fn enable_peak(&mut self) -> Option< () > {
+        self.writer().enable_peak()
+    }
+
+    pub fn set_start_timecode(&mut self, timecode: Timecode) -> Option< () > {
+        self.writer().set_start_timecode(timecode)
+    }
+
+    pub fn pos(&mut self) -> usize {
+        self.writer.pos()
+    }
+
+    pub fn len(&self) -> usize {
+        self.writer.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.writer.is_empty()
+    }
+
+    pub fn format(&self) -> FileFormat {
+        self.writer.format()
+    }
+
+    pub fn skip(&mut self, n: usize) -> Option< () > {
+        self.writer().skip(n)
+    }
+
+    pub fn rewind(&mut self, n: usize) -> Option< () > {
+        self.writer().rewind(n)
+    }
+
+    pub fn seek(&mut self, n: usize) -> Option< () > {
+        self.writer().seek(n)
+    }
+
+    pub async fn write(&mut self, from: &[f32]) -> Option< () > {
+        self.writer().write(from)?;
+        self.drain().await
+    }
+
+    pub async fn flush(&mut self) -> Option< () > {
+        self.drain().await?;
+        self.saver.flush().await
+    }
+
+    pub async fn finalize(&mut self) -> Option< () > {
+        self.writer.finalize()?;
+        self.flush().await?;
+        self.pending.0 = false;
+        Some(())
+    }
+
+    pub async fn into_inner(mut self) -> Option< T > {
+        self.finalize().await?;
+        Some(self.saver.into_inner())
+    }
+
+    fn writer(&mut self) -> &mut Writer< Journal > {
+        self.pending.0 = true;
+        &mut self.writer
+    }
+
+    async fn drain(&mut self) -> Option< () > {
+        for (offset, data) in self.writer.get_mut().take() {
+            self.saver.seek(offset).await?;
+            self.saver.save(data.as_slice()).await?;
+        }
+
+        Some(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use crate::{ utils::fixture::{ Memory, block_on, read, format }, common::{ Sample, FileFormat, ChunkPosition }, metadata::Bext, writer::Writer, async_reader::AsyncReader };
+    use super::AsyncWriter;
+
+    const SAMPLES: [f32; 5] = [0.5, -0.5, 0.25, -0.25, 0.125];
+
+    #[test]
+    fn matches_writer() {
+        for rf64 in [false, true] {
+            let format = FileFormat::new(Sample::I24, 1, 8000);
+            let bext   = Bext { description: "async".to_owned(), ..Bext::default() };
+
+            let mut file   = Cursor::new(Vec::new());
+            let mut writer = if rf64 { Writer::to_rf64(&mut file, format) } else { Writer::to(&mut file, format) }.unwrap();
+            writer.add_bext(&bext).unwrap();
+            writer.write(&SAMPLES).unwrap();
+            writer.add_chunk(b"wxyz", b"odd", ChunkPosition::AfterData).unwrap();
+            writer.finalize().unwrap();
+            drop(writer);
+
+            let memory     = Memory(Cursor::new(Vec::new()));
+            let mut writer = if rf64 { AsyncWriter::to_rf64(memory, format) } else { AsyncWriter::to(memory, format) }.unwrap();
+            writer.add_bext(&bext).unwrap();
+            block_on(writer.write(&SAMPLES[ ..2 ])).unwrap();
+            block_on(writer.write(&SAMPLES[ 2.. ])).unwrap();
+            writer.add_chunk(b"wxyz", b"odd", ChunkPosition::AfterData).unwrap();
+            let out = block_on(writer.into_inner()).unwrap().0.into_inner();
+            assert_eq!(out, file.into_inner());
+
+            let mut reader = block_on(AsyncReader::from(Memory(Cursor::new(out.clone())))).unwrap();
+            let mut a      = [0f32; 5];
+            let mut b      = [0f32; 5];
+            read(out).read(&mut a).unwrap();
+            block_on(reader.read(&mut b)).unwrap();
+            assert_eq!(a, b);
+            assert_eq!(block_on(reader.bext()).unwrap().description, "async");
+        }
+    }
+
+    #[test]
+    fn rejects_misplaced_chunks() {
+        let mut writer = AsyncWriter::to(Memory(Cursor::new(Vec::new())), format()).unwrap();
+        assert!(writer.add_chunk(b"data", b"", ChunkPosition::BeforeData).is_none());
+        block_on(writer.write(&SAMPLES)).unwrap();
+        assert!(writer.add_chunk(b"abcd", b"", ChunkPosition::BeforeData).is_none());
+        assert!(writer.enable_peak().is_none());
+
+        let out = block_on(writer.into_inner()).unwrap().0.into_inner();
+        assert_eq!(read(out).chunks().len(), 2);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "finalize")]
+    fn catches_unfinalized_drop() {
+        let mut writer = AsyncWriter::to(Memory(Cursor::new(Vec::new())), format()).unwrap();
+        block_on(writer.finalize()).unwrap();
+        block_on(writer.write(&SAMPLES)).unwrap();
+        drop(writer);
+    }
+}
@@ -0,0 +1,49 @@
+use std::io::{ Read, Seek, Error, ErrorKind };
+use crate::{ utils::Loader, common::Chunk };
+
+pub struct ChunkReader< 'a, F: Read + Seek > {
+    loader : &'a mut Loader< F >,
+    pos    : usize              ,
+    end    : usize              ,
+    restore: usize
+}
+
+impl< 'a, F: Read + Seek > ChunkReader< 'a, F > {
+    pub(crate) fn new(loader: &'a mut Loader< F >, chunk: &Chunk) -> Self {
+        let restore = loader.pos();
+        let pos     = chunk.data_offset();
+        let end     = pos + chunk.size();
+
+        Self {
+            loader ,
+            pos    ,
+            end    ,
+            restore
+        }
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.end - self.pos
+    }
+}
+
+impl< F: Read + Seek > Read for ChunkReader< '_, F > {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result< usize > {
+        let n = buf.len().min(self.remaining());
+
+        if n == 0 {
+            return Ok(0)
+        }
+
+        self.loader.seek(self.pos).ok_or(Error::from(ErrorKind::Other))?;
+        let n = self.loader.read(&mut buf[ ..n ]).ok_or(Error::from(ErrorKind::Other))?;
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+impl< F: Read + Seek > Drop for ChunkReader< '_, F > {
+    fn drop(&mut self) {
+        self.loader.seek(self.restore);
+    }
+}
@@ -0,0 +1,51 @@
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ChunkPosition {
+    BeforeData,
+    Data      ,
+    AfterData
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Chunk {
+    id      : [u8; 4]      ,
+    offset  : usize        ,
+    size    : usize        ,
+    position: ChunkPosition
+}
+
+impl Chunk {
+    pub fn new(id: [u8; 4], offset: usize, size: usize, position: ChunkPosition) -> Self {
+        Self {
+            id      ,
+            offset  ,
+            size    ,
+            position
+        }
+    }
+
+    pub fn id(&self) -> [u8; 4] {
+        self.id
+    }
+
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn data_offset(&self) -> usize {
+        self.offset + 8
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn position(&self) -> ChunkPosition {
+        self.position
+    }
+}
+
+impl std::fmt::Display for Chunk {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} @{} ({} bytes)", String::from_utf8_lossy(&self.id), self.offset, self.size)
+    }
+}
@@ -0,0 +1,93 @@
+use super::Sample;
+
+pub(crate) fn decode(sample: Sample, from: &[u8], to: &mut [f32]) {
+    match sample {
+        Sample::U8 => {
+            const A: f32 = 2.0 / u8::MAX as f32;
+
+            for (x, &value) in to.iter_mut().zip(from) {
+                *x = (value as f32 * A) - 1.0;
+            }
+        },
+        Sample::I16 => {
+            const A: f32 = 1.0 / i16::MAX as f32;
+
+            for (x, bytes) in to.iter_mut().zip(from.chunks_exact(2)) {
+                let value = i16::from_le_bytes([bytes[ 0 ], bytes[ 1 ]]);
+                *x = value as f32 * A;
+            }
+        },
+        Sample::I24 => {
+            const A: f32 = 1.0 / 0x7FFFFF as f32;
+
+            for (x, bytes) in to.iter_mut().zip(from.chunks_exact(3)) {
+                let third = (bytes[ 2 ] >> 7) * 0xFF;
+                let value = i32::from_le_bytes([bytes[ 0 ], bytes[ 1 ], bytes[ 2 ], third]);
+                *x = value as f32 * A;
+            }
+        },
+        Sample::I32 => {
+            const A: f32 = 1.0 / i32::MAX as f32;
+
+            for (x, bytes) in to.iter_mut().zip(from.chunks_exact(4)) {
+                let value = i32::from_le_bytes([bytes[ 0 ], bytes[ 1 ], bytes[ 2 ], bytes[ 3 ]]);
+                *x = value as f32 * A;
+            }
+        },
+        Sample::F32 => {
+            for (x, bytes) in to.iter_mut().zip(from.chunks_exact(4)) {
+                *x = f32::from_le_bytes([bytes[ 0 ], bytes[ 1 ], bytes[ 2 ], bytes[ 3 ]]);
+            }
+        },
+        Sample::F64 => {
+            for (x, bytes) in to.iter_mut().zip(from.chunks_exact(8)) {
+                *x = f64::from_le_bytes(bytes.try_into().unwrap()) as f32;
+            }
+        }
+    }
+}
+
+pub(crate) fn encode(sample: Sample, from: &[f32], to: &mut Vec< u8 >) {
+    to.reserve(from.len() * sample.depth() as usize);
+
+    match sample {
+        Sample::U8 => {
+            const A: f32 = u8::MAX as f32 / 2.0;
+
+            for x in from {
+                to.push(((x + 1.0) * A) as u8);
+            }
+        },
+        Sample::I16 => {
+            const A: f32 = i16::MAX as f32;
+
+            for x in from {
+                to.extend_from_slice(&((x * A) as i16).to_le_bytes());
+            }
+        },
+        Sample::I24 => {
+            const A: f32 = 0x7FFFFF as f32;
+
+            for x in from {
+                to.extend_from_slice(&((x * A) as i32).to_le_bytes()[ ..3 ]);
+            }
+        },
+        Sample::I32 => {
+            const A: f32 = i32::MAX as f32;
+
+            for x in from {
+                to.extend_from_slice(&((x * A) as i32).to_le_bytes());
+            }
+        },
+        Sample::F32 => {
+            for x in from {
+                to.extend_from_slice(&x.to_le_bytes());
+            }
+        },
+        Sample::F64 => {
+            for x in from {
+                to.extend_from_slice(&(*x as f64).to_le_bytes());
+            }
+        }
+    }
+}
@@ -0,0 +1,111 @@
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Warning,
+    Error
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Issue {
+    NotWave                                            ,
+    RiffSizeMismatch   { stored: usize, actual: usize },
+    Ds64SizeMismatch   { stored: usize, actual: usize },
+    ByteRateMismatch   { stored: usize, actual: usize },
+    BlockAlignMismatch { stored: usize, actual: usize },
+    DataSizeUnset      { stored: usize                },
+    DataTruncated      { stored: usize, actual: usize },
+    PartialFrame       { extra : usize                },
+    SampleCountMismatch{ stored: usize, actual: usize },
+    Ds64DataMismatch   { stored: usize, actual: usize },
+    ChunkTruncated     { id    : [u8; 4]              },
+    MissingPad         { id    : [u8; 4]              },
+    MissingChunk       { id    : [u8; 4]              },
+    DuplicateChunk     { id    : [u8; 4]              },
+    MisplacedChunk     { id    : [u8; 4]              },
+    UnsupportedFormat  { audio_format: u16, bit_depth: u16 },
+    EmptyData                                          ,
+    TrailingBytes      { count : usize                },
+    FactMismatch       { stored: usize, actual: usize },
+    MissingFact
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Diagnostic {
+    offset: usize,
+    issue : Issue
+}
+
+impl Issue {
+    pub fn severity(&self) -> Severity {
+        match self {
+            Issue::UnsupportedFormat { .. } |
+            Issue::EmptyData                |
+            Issue::TrailingBytes     { .. } |
+            Issue::FactMismatch      { .. } |
+            Issue::MissingFact              => Severity::Warning,
+            _                               => Severity::Error
+        }
+    }
+}
+
+impl Diagnostic {
+    pub fn new(offset: usize, issue: Issue) -> Self {
+        Self {
+            offset,
+            issue
+        }
+    }
+
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn issue(&self) -> Issue {
+        self.issue
+    }
+
+    pub fn severity(&self) -> Severity {
+        self.issue.severity()
+    }
+}
+
+impl std::fmt::Display for Issue {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Issue::NotWave                                => write!(f, "not a RIFF/RF64 WAVE file"),
+            Issue::RiffSizeMismatch    { stored, actual } => write!(f, "RIFF size is {} but should be {}"        , stored, actual),
+            Issue::Ds64SizeMismatch    { stored, actual } => write!(f, "ds64 file size is {} but should be {}"   , stored, actual),
+            Issue::ByteRateMismatch    { stored, actual } => write!(f, "byte rate is {} but should be {}"        , stored, actual),
+            Issue::BlockAlignMismatch  { stored, actual } => write!(f, "block align is {} but should be {}"      , stored, actual),
+            Issue::DataSizeUnset       { stored         } => write!(f, "data size {:#X} was never finalized"     , stored        ),
+            Issue::DataTruncated       { stored, actual } => write!(f, "data size is {} but only {} bytes remain", stored, actual),
+            Issue::PartialFrame        { extra          } => write!(f, "data ends with {} bytes of a partial frame", extra       ),
+            Issue::SampleCountMismatch { stored, actual } => write!(f, "sample count is {} but should be {}"     , stored, actual),
+            Issue::Ds64DataMismatch    { stored, actual } => write!(f, "ds64 data size is {} but should be {}"   , stored, actual),
+            Issue::ChunkTruncated      { id             } => write!(f, "{} chunk is truncated"                   , String::from_utf8_lossy(id)),
+            Issue::MissingPad          { id             } => write!(f, "{} chunk is missing its pad byte"        , String::from_utf8_lossy(id)),
+            Issue::MissingChunk        { id             } => write!(f, "{} chunk is missing"                     , String::from_utf8_lossy(id)),
+            Issue::DuplicateChunk      { id             } => write!(f, "{} chunk appears more than once"         , String::from_utf8_lossy(id)),
+            Issue::MisplacedChunk      { id             } => write!(f, "{} chunk is out of place"                , String::from_utf8_lossy(id)),
+            Issue::UnsupportedFormat   { audio_format, bit_depth } => write!(f, "format {} with {} bits is not supported", audio_format, bit_depth),
+            Issue::EmptyData                              => write!(f, "data chunk is empty"),
+            Issue::TrailingBytes       { count          } => write!(f, "{} trailing bytes after the last chunk"  , count         ),
+            Issue::FactMismatch        { stored, actual } => write!(f, "fact sample length is {} but should be {}", stored, actual),
+            Issue::MissingFact                            => write!(f, "fact chunk is missing for a non-PCM format")
+        }
+    }
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "@{}: {}: {}", self.offset, self.severity(), self.issue)
+    }
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error   => write!(f, "error"  )
+        }
+    }
+}
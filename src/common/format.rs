@@ -30,6 +30,10 @@ impl AudioFormat {
             _ => None
         }
     }
+
+    pub fn requires_fact(&self) -> bool {
+        *self != AudioFormat::Pcm
+    }
 }
 
 impl Sample {
@@ -56,6 +60,17 @@ impl Sample {
         }
     }
 
+    pub fn bit_depth(&self) -> u16 {
+        self.depth() * 8
+    }
+
+    pub(crate) fn silence(&self) -> u8 {
+        match self {
+            Sample::U8 => 0x80,
+            _          => 0
+        }
+    }
+
     pub fn audio_format(&self) -> AudioFormat {
         match self {
             Sample::U8  => AudioFormat::Pcm      ,
@@ -90,11 +105,11 @@ impl FileFormat {
     }
 
     pub fn byte_rate(&self) -> u32 {
-        self.sample_rate * self.num_channels as u32 * self.sample.depth() as u32 / 8
+        self.sample_rate * self.num_channels as u32 * self.sample.depth() as u32
     }
 
     pub fn block_align(&self) -> u16 {
-        self.num_channels * self.sample.depth() / 8
+        self.num_channels * self.sample.depth()
     }
 }
 
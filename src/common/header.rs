@@ -0,0 +1,72 @@
+use super::FileFormat;
+
+pub const RIFF_HEADER_SIZE: usize = 12;
+pub const DS64_CHUNK_SIZE : usize = 36;
+pub const FMT_CHUNK_SIZE  : usize = 24;
+pub const FACT_CHUNK_SIZE : usize = 12;
+
+// Everything up to the first data byte. Sizes that are not known yet are left at their unknown-length markers.
+pub fn header(format: FileFormat, rf64: bool, frames: Option< usize >) -> Option< Vec< u8 > > {
+    let fact      = format.sample().audio_format().requires_fact();
+    let data_size = match frames {
+        Some(frames) => Some(frames.checked_mul(format.block_align() as usize)?),
+        None         => None
+    };
+    let len       = RIFF_HEADER_SIZE
+                  + if rf64 { DS64_CHUNK_SIZE } else { 0 }
+                  + FMT_CHUNK_SIZE
+                  + if fact { FACT_CHUNK_SIZE } else { 0 }
+                  + 8;
+    let file_size = match data_size {
+        Some(data_size) => Some(len.checked_add(data_size)?.checked_add(data_size % 2)?),
+        None            => None
+    };
+    let mut ret   = Vec::with_capacity(len);
+
+    if rf64 {
+        ret.extend_from_slice(b"RF64");
+        ret.extend_from_slice(&0xFFFFFFFFu32.to_le_bytes());
+        ret.extend_from_slice(b"WAVE");
+
+        ret.extend_from_slice(b"ds64");
+        ret.extend_from_slice(&(DS64_CHUNK_SIZE as u32 - 8).to_le_bytes());
+        ret.extend_from_slice(&file_size.map_or(u64::MAX, |file_size| file_size as u64 - 8).to_le_bytes());
+        ret.extend_from_slice(&data_size.map_or(u64::MAX, |data_size| data_size as u64    ).to_le_bytes());
+        ret.extend_from_slice(&frames   .map_or(u64::MAX, |frames   | frames    as u64    ).to_le_bytes());
+        ret.extend_from_slice(&0u32.to_le_bytes()); // Table length
+    }
+    else {
+        let riff_size = match file_size {
+            Some(file_size) => u32::try_from(file_size - 8).ok()?,
+            None            => 0xFFFFFFFF
+        };
+
+        ret.extend_from_slice(b"RIFF");
+        ret.extend_from_slice(&riff_size.to_le_bytes());
+        ret.extend_from_slice(b"WAVE");
+    }
+
+    ret.extend_from_slice(b"fmt ");
+    ret.extend_from_slice(&(FMT_CHUNK_SIZE as u32 - 8).to_le_bytes());
+    ret.extend_from_slice(&(format.sample().audio_format() as u16).to_le_bytes());
+    ret.extend_from_slice(& format         .num_channels().to_le_bytes());
+    ret.extend_from_slice(& format         .sample_rate ().to_le_bytes());
+    ret.extend_from_slice(& format         .byte_rate   ().to_le_bytes());
+    ret.extend_from_slice(& format         .block_align ().to_le_bytes());
+    ret.extend_from_slice(& format.sample().bit_depth   ().to_le_bytes());
+
+    if fact {
+        ret.extend_from_slice(b"fact");
+        ret.extend_from_slice(&4u32.to_le_bytes());
+        ret.extend_from_slice(&frames.and_then(|frames| u32::try_from(frames).ok()).unwrap_or(0xFFFFFFFF).to_le_bytes());
+    }
+
+    ret.extend_from_slice(b"data");
+
+    match data_size {
+        Some(data_size) if !rf64 => ret.extend_from_slice(&(data_size as u32).to_le_bytes()),
+        _                        => ret.extend_from_slice(&0xFFFFFFFFu32.to_le_bytes())
+    }
+
+    Some(ret)
+}
@@ -1,3 +1,13 @@
 mod format;
+mod chunk;
+mod options;
+mod diagnostic;
+mod convert;
+mod header;
 
 pub use format::{ AudioFormat, Sample, FileFormat };
+pub use chunk::{ ChunkPosition, Chunk };
+pub use options::ReadOptions;
+pub use diagnostic::{ Severity, Issue, Diagnostic };
+pub(crate) use convert::{ decode, encode };
+pub(crate) use header::{ header, RIFF_HEADER_SIZE, DS64_CHUNK_SIZE };
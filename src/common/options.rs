@@ -0,0 +1,29 @@
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub struct ReadOptions {
+    tolerate_missing_pad: bool,
+    recover             : bool
+}
+
+impl ReadOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_tolerate_missing_pad(mut self, tolerate_missing_pad: bool) -> Self {
+        self.tolerate_missing_pad = tolerate_missing_pad;
+        self
+    }
+
+    pub fn with_recover(mut self, recover: bool) -> Self {
+        self.recover = recover;
+        self
+    }
+
+    pub fn tolerate_missing_pad(&self) -> bool {
+        self.tolerate_missing_pad || self.recover
+    }
+
+    pub fn recover(&self) -> bool {
+        self.recover
+    }
+}
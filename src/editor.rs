@@ -0,0 +1,339 @@
+use std::io::{ Read, Write, Seek };
+use crate::{ utils::{ Loader, Saver }, metadata::{ Bext, Info, Id3Tag, Ixml, Cart, parse_list }, common::Chunk, reader::Reader };
+
+pub struct Editor< F: Read + Write + Seek > {
+    file  : F           ,
+    chunks: Vec< Chunk >,
+    len   : usize       ,
+    rf64  : bool
+}
+
+impl< F: Read + Write + Seek > Editor< F > {
+    pub fn open(mut file: F) -> Option< Editor< F > > {
+        let (chunks, len) = Self::scan(&mut file)?;
+        let rf64          = chunks.iter().any(|chunk| &chunk.id() == b"ds64");
+        Some(Editor { file, chunks, len, rf64 })
+    }
+
+    fn scan(file: &mut F) -> Option< (Vec< Chunk >, usize) > {
+        let mut loader = Loader::from(&mut *file);
+        let len        = loader.len();
+        loader.seek(0)?;
+
+        let chunks = Reader::from(&mut *file)?.chunks().to_vec();
+        Some((chunks, len))
+    }
+
+    pub fn chunks(&self) -> &[Chunk] {
+        &self.chunks
+    }
+
+    pub fn find_chunk(&self, id: &[u8; 4]) -> Option< Chunk > {
+        self.chunks.iter().find(|chunk| &chunk.id() == id).copied()
+    }
+
+    pub fn read_chunk(&mut self, chunk: &Chunk) -> Option< Vec< u8 > > {
+        let mut ret    = vec![0u8; chunk.size()];
+        let mut loader = Loader::from(&mut self.file);
+        loader.seek(chunk.data_offset())?;
+        loader.load(ret.as_mut_slice())?;
+        Some(ret)
+    }
+
+    fn find_list(&mut self, list_type: &[u8; 4]) -> Option< Chunk > {
+        let lists = self.chunks.iter().filter(|chunk| &chunk.id() == b"LIST").copied().collect::< Vec< _ > >();
+
+        for chunk in lists {
+            let mut loader = Loader::from(&mut self.file);
+            loader.seek(chunk.data_offset())?;
+
+            if chunk.size() >= 4 && &loader.cload::< [u8; 4] >()? == list_type {
+                return Some(chunk)
+            }
+        }
+
+        None
+    }
+
+    fn holds_audio(&mut self, chunk: &Chunk) -> Option< bool > {
+        if &chunk.id() != b"LIST" {
+            return Some(false)
+        }
+
+        Some(is_wave_list(&self.read_chunk(chunk)?))
+    }
+
+    pub fn set_chunk(&mut self, id: &[u8; 4], data: &[u8]) -> Option< () > {
+        if is_protected(id) || (id == b"LIST" && is_wave_list(data)) {
+            return None
+        }
+
+        let target = match id {
+            b"LIST" => self.find_list(data.get(..4)?.try_into().ok()?),
+            _       => self.find_chunk(id)
+        };
+
+        if let Some(target) = target {
+            if self.holds_audio(&target)? {
+                return None
+            }
+        }
+
+        self.put(target, id, data)
+    }
+
+    pub fn remove_chunk(&mut self, id: &[u8; 4]) -> Option< () > {
+        if is_protected(id) {
+            return None
+        }
+
+        let target = self.find_chunk(id)?;
+
+        if self.holds_audio(&target)? {
+            return None
+        }
+
+        self.remove(target)
+    }
+
+    pub fn remove_list(&mut self, list_type: &[u8; 4]) -> Option< () > {
+        let target = self.find_list(list_type)?;
+
+        if self.holds_audio(&target)? {
+            return None
+        }
+
+        self.remove(target)
+    }
+
+    pub fn set_bext(&mut self, bext: &Bext) -> Option< () > {
+        self.set_chunk(b"bext", &bext.to_bytes()?)
+    }
+
+    pub fn set_info(&mut self, info: &Info) -> Option< () > {
+        self.set_chunk(b"LIST", &info.to_bytes())
+    }
+
+    pub fn set_id3(&mut self, id3: &Id3Tag) -> Option< () > {
+        self.set_chunk(b"id3 ", &id3.to_bytes())
+    }
+
+    pub fn set_ixml(&mut self, ixml: &Ixml) -> Option< () > {
+        self.set_chunk(b"iXML", ixml.to_xml().as_bytes())
+    }
+
+    pub fn set_xmp(&mut self, xmp: &str) -> Option< () > {
+        self.set_chunk(b"_PMX", xmp.as_bytes())
+    }
+
+    pub fn set_cart(&mut self, cart: &Cart) -> Option< () > {
+        self.set_chunk(b"cart", &cart.to_bytes()?)
+    }
+
+    pub fn into_inner(self) -> F {
+        self.file
+    }
+
+    fn free_runs(&self, target: Option< Chunk >) -> Vec< (usize, usize) > {
+        let mut runs = Vec::< (usize, usize) >::new();
+
+        for chunk in &self.chunks {
+            if !(is_filler(&chunk.id()) || Some(*chunk) == target) {
+                continue
+            }
+
+            let end = chunk.offset() + slot(chunk);
+
+            match runs.last_mut() {
+                Some(run) if run.1 == chunk.offset() => run.1 = end,
+                _                                    => runs.push((chunk.offset(), end))
+            }
+        }
+
+        runs
+    }
+
+    fn put(&mut self, target: Option< Chunk >, id: &[u8; 4], data: &[u8]) -> Option< () > {
+        u32::try_from(data.len()).ok()?;
+
+        let need = 8 + data.len() + data.len() % 2;
+        let len  = self.len;
+        let fits = |&(begin, end): &(usize, usize)| {
+            let room = end - begin;
+            room == need || room >= need + 8 || (end >= len && need > room)
+        };
+        let owns = |&(begin, end): &(usize, usize)| target.is_some_and(|target| (begin..end).contains(&target.offset()));
+        let runs = self.free_runs(target);
+        let run  = runs.iter().copied().filter(fits).find(owns).or_else(|| runs.iter().copied().find(fits));
+
+        let (begin, end) = run.unwrap_or((len + len % 2, len + len % 2));
+        let new_len      = len.max(begin + need);
+
+        if !self.rf64 {
+            u32::try_from(new_len - 8).ok()?;
+        }
+
+        let mut saver = Saver::to(&mut self.file);
+
+        if run.is_none() && len % 2 == 1 {
+            saver.seek(len)?;
+            saver.save(&0u8)?;
+        }
+
+        if let Some(target) = target.filter(|target| !(begin..end).contains(&target.offset())) {
+            saver.seek(target.offset())?;
+            saver.save(b"JUNK")?;
+        }
+
+        saver.seek(begin)?;
+        saver.save_chunk(id, data)?;
+
+        if end > begin + need {
+            saver.save_chunk(b"JUNK", &vec![0u8; end - begin - need - 8])?;
+        }
+
+        if new_len != len {
+            self.save_file_size(new_len)?;
+        }
+
+        self.refresh()
+    }
+
+    fn remove(&mut self, target: Chunk) -> Option< () > {
+        let mut saver = Saver::to(&mut self.file);
+        saver.seek(target.offset())?;
+        saver.save(b"JUNK")?;
+        self.refresh()
+    }
+
+    fn save_file_size(&mut self, len: usize) -> Option< () > {
+        let mut saver = Saver::to(&mut self.file);
+
+        match self.chunks.iter().find(|chunk| &chunk.id() == b"ds64") {
+            Some(ds64) => {
+                saver.seek(ds64.data_offset())?;
+                saver.save(&(len as u64 - 8))
+            },
+            None => {
+                saver.seek(4)?;
+                saver.save(&((len - 8) as u32))
+            }
+        }
+    }
+
+    fn refresh(&mut self) -> Option< () > {
+        let (chunks, len) = Self::scan(&mut self.file)?;
+        self.chunks = chunks;
+        self.len    = len;
+        Some(())
+    }
+}
+
+pub fn replace_xmp< F: Read + Write + Seek >(file: &mut F, xmp: &str) -> Option< () > {
+    Editor::open(file)?.set_xmp(xmp)
+}
+
+fn is_filler(id: &[u8; 4]) -> bool {
+    matches!(id, b"JUNK" | b"junk" | b"FLLR" | b"PAD ")
+}
+
+fn is_protected(id: &[u8; 4]) -> bool {
+    matches!(id, b"RIFF" | b"RF64" | b"ds64" | b"fmt " | b"fact" | b"data" | b"JUNK")
+}
+
+fn is_wave_list(list: &[u8]) -> bool {
+    list.starts_with(b"wavl") || parse_list(list).is_some_and(|(_, items)| items.iter().any(|(id, _)| matches!(id, b"data" | b"slnt")))
+}
+
+fn slot(chunk: &Chunk) -> usize {
+    8 + chunk.size() + chunk.size() % 2
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use crate::{ utils::fixture::{ written, read, chunk, riff, fmt_pcm16, pcm16, format }, common::ChunkPosition, metadata::Bext };
+    use super::{ Editor, replace_xmp };
+
+    fn samples(file: Vec< u8 >) -> Vec< f32 > {
+        let mut reader = read(file);
+        let mut ret    = vec![0f32; reader.len()];
+        reader.read(&mut ret).unwrap();
+        ret
+    }
+
+    #[test]
+    fn replaces_xmp() {
+        let original = written(format(), |writer| {
+            writer.add_xmp("<x:xmpmeta>first</x:xmpmeta>").unwrap();
+            writer.write(&[0.5, -0.5, 0.25]).unwrap();
+        });
+        assert_eq!(read(original.clone()).xmp().as_deref(), Some("<x:xmpmeta>first</x:xmpmeta>"));
+
+        let mut file = Cursor::new(original.clone());
+        replace_xmp(&mut file, "<x:xmpmeta>a much longer second packet</x:xmpmeta>").unwrap();
+        replace_xmp(&mut file, "<x/>").unwrap();
+
+        let edited = file.into_inner();
+        assert_eq!(read(edited.clone()).xmp().as_deref(), Some("<x/>"));
+        assert_eq!(read(edited.clone()).chunks().iter().filter(|chunk| &chunk.id() == b"_PMX").count(), 1);
+        assert_eq!(samples(edited), samples(original));
+    }
+
+    #[test]
+    fn rejects_non_wave_files() {
+        assert!(replace_xmp(&mut Cursor::new(b"RIFF\x04\x00\x00\x00AVI ".to_vec()), "<x/>").is_none());
+        assert!(replace_xmp(&mut Cursor::new(Vec::new()), "<x/>").is_none());
+
+        let file       = written(format(), |writer| writer.add_chunk(b"abcd", b"", ChunkPosition::BeforeData).unwrap());
+        let mut editor = Editor::open(Cursor::new(file)).unwrap();
+        assert!(editor.set_chunk(b"LIST", b"IN").is_none());
+    }
+
+    #[test]
+    fn edits_in_place() {
+        let original = written(format(), |writer| {
+            writer.add_chunk(b"JUNK", &[0; 700], ChunkPosition::BeforeData).unwrap();
+            writer.write(&[0.5, -0.5]).unwrap();
+        });
+        let data = read(original.clone()).find_chunk(b"data").unwrap();
+
+        let mut editor = Editor::open(Cursor::new(original.clone())).unwrap();
+        editor.set_bext(&Bext { description: "in place".to_owned(), ..Bext::default() }).unwrap();
+        editor.set_chunk(b"wxyz", &[1; 1000]).unwrap();
+        editor.remove_chunk(b"wxyz").unwrap();
+
+        let edited = editor.into_inner().into_inner();
+        assert_eq!(edited.len(), original.len() + 1008);
+
+        let mut reader = read(edited.clone());
+        assert!(reader.find_chunk(b"data").unwrap() == data);
+        assert!(reader.find_chunk(b"wxyz").is_none());
+        assert_eq!(reader.bext().unwrap().description, "in place");
+        assert_eq!(samples(edited), samples(original));
+    }
+
+    #[test]
+    fn rejects_structural_chunks() {
+        let file       = written(format(), |writer| writer.write(&[0.0]).unwrap());
+        let mut editor = Editor::open(Cursor::new(file.clone())).unwrap();
+
+        for id in [b"RIFF", b"RF64", b"ds64", b"fmt ", b"fact", b"data", b"JUNK"] {
+            assert!(editor.set_chunk(id, b"").is_none());
+            assert!(editor.remove_chunk(id).is_none());
+        }
+
+        assert!(editor.remove_chunk(b"bext").is_none());
+        assert!(editor.remove_list(b"INFO").is_none());
+        assert!(editor.set_chunk(b"LIST", b"wavl").is_none());
+        assert!(editor.set_chunk(b"LIST", &[&b"adtl"[ .. ], &chunk(b"slnt", &[0; 4])].concat()).is_none());
+        assert_eq!(editor.into_inner().into_inner(), file);
+
+        let wavl       = [&b"wavl"[ .. ], &chunk(b"slnt", &1u32.to_le_bytes())].concat();
+        let file       = riff(&[fmt_pcm16(1, 8000), chunk(b"data", &pcm16(&[1])), chunk(b"LIST", &wavl)]);
+        let mut editor = Editor::open(Cursor::new(file.clone())).unwrap();
+        assert!(editor.remove_list(b"wavl").is_none());
+        assert!(editor.remove_chunk(b"LIST").is_none());
+        assert_eq!(editor.into_inner().into_inner(), file);
+    }
+}
@@ -1,11 +1,32 @@
-#![feature(let_chains)]
-#![feature(slice_index_methods)]
 #![feature(seek_stream_len)]
 
 mod common;
+mod metadata;
 mod utils;
 mod reader;
 mod writer;
+mod chunk_reader;
+mod validator;
+mod editor;
+mod strip;
+mod stream_reader;
+mod stream_writer;
+#[cfg(feature = "async")]
+mod async_reader;
+#[cfg(feature = "async")]
+mod async_writer;
 
+pub use common::{ AudioFormat, Sample, FileFormat, ChunkPosition, Chunk, ReadOptions, Severity, Issue, Diagnostic };
+pub use metadata::{ Loudness, Bext, FrameRate, Timecode, Info, Id3Frame, Id3Tag, CuePoint, Cues, LoopType, SampleLoop, Sampler, Instrument, Acid, IxmlTrack, IxmlSpeed, Ixml, PeakEnvelope, CartTimer, Cart, ChannelPeak, Peak };
 pub use reader::Reader;
 pub use writer::Writer;
+pub use chunk_reader::ChunkReader;
+pub use validator::{ Report, validate };
+pub use editor::{ Editor, replace_xmp };
+pub use strip::strip_metadata;
+pub use stream_reader::StreamReader;
+pub use stream_writer::StreamWriter;
+#[cfg(feature = "async")]
+pub use async_reader::AsyncReader;
+#[cfg(feature = "async")]
+pub use async_writer::AsyncWriter;
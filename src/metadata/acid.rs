@@ -0,0 +1,108 @@
+use std::io::Cursor;
+use crate::utils::{ Loader, Saver };
+
+const ACID_SIZE      : usize = 24;
+const FLAG_ONE_SHOT  : u32   = 0x01;
+const FLAG_ROOT_NOTE : u32   = 0x02;
+const FLAG_STRETCH   : u32   = 0x04;
+const FLAG_DISK_BASED: u32   = 0x08;
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct Acid {
+    pub one_shot         : bool         ,
+    pub stretch          : bool         ,
+    pub disk_based       : bool         ,
+    pub root_note        : Option< u16 >,
+    pub num_beats        : u32          ,
+    pub meter_numerator  : u16          ,
+    pub meter_denominator: u16          ,
+    pub tempo            : f32
+}
+
+impl Default for Acid {
+    fn default() -> Self {
+        Self {
+            one_shot         : false,
+            stretch          : true ,
+            disk_based       : false,
+            root_note        : None ,
+            num_beats        : 0    ,
+            meter_numerator  : 4    ,
+            meter_denominator: 4    ,
+            tempo            : 120.0
+        }
+    }
+}
+
+impl Acid {
+    pub fn from_bytes(bytes: &[u8]) -> Option< Self > {
+        if bytes.len() < ACID_SIZE {
+            return None
+        }
+
+        let mut loader = Loader::from(Cursor::new(bytes));
+
+        let flags            : u32 = loader.cload()?;
+        let root_note        : u16 = loader.cload()?;
+        loader.skip(6)?;
+        let num_beats        : u32 = loader.cload()?;
+        let meter_denominator: u16 = loader.cload()?;
+        let meter_numerator  : u16 = loader.cload()?;
+        let tempo            : f32 = loader.cload()?;
+
+        Some(Self {
+            one_shot  : flags & FLAG_ONE_SHOT   != 0,
+            stretch   : flags & FLAG_STRETCH    != 0,
+            disk_based: flags & FLAG_DISK_BASED != 0,
+            root_note : if flags & FLAG_ROOT_NOTE != 0 { Some(root_note) } else { None },
+            num_beats        ,
+            meter_numerator  ,
+            meter_denominator,
+            tempo
+        })
+    }
+
+    pub fn to_bytes(&self) -> Option< Vec< u8 > > {
+        let mut saver = Saver::to(Cursor::new(Vec::new()));
+
+        let flags = if self.one_shot            { FLAG_ONE_SHOT   } else { 0 }
+                  | if self.root_note.is_some() { FLAG_ROOT_NOTE  } else { 0 }
+                  | if self.stretch             { FLAG_STRETCH    } else { 0 }
+                  | if self.disk_based          { FLAG_DISK_BASED } else { 0 };
+
+        saver.save(&flags                       )?;
+        saver.save(&self.root_note.unwrap_or(60))?;
+        saver.save(&0x8000u16                   )?;
+        saver.save(&0f32                        )?;
+        saver.save(&self.num_beats              )?;
+        saver.save(&self.meter_denominator      )?;
+        saver.save(&self.meter_numerator        )?;
+        saver.save(&self.tempo                  )?;
+
+        Some(saver.into_inner().into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::fixture::{ written, read, format };
+    use super::Acid;
+
+    #[test]
+    fn round_trips() {
+        let acid = Acid { one_shot: true, stretch: false, root_note: Some(57), num_beats: 16, meter_numerator: 3, tempo: 97.5, ..Acid::default() };
+        let file = written(format(), |writer| {
+            writer.write(&[0.0]).unwrap();
+            writer.add_acid(&acid).unwrap();
+        });
+        assert!(read(file).acid().unwrap() == acid);
+        assert!(Acid::from_bytes(&Acid::default().to_bytes().unwrap()).unwrap() == Acid::default());
+    }
+
+    #[test]
+    fn rejects_short_chunk() {
+        let bytes = Acid::default().to_bytes().unwrap();
+        assert!(Acid::from_bytes(&bytes[ ..23 ]).is_none());
+        assert!(Acid::from_bytes(&[]).is_none());
+    }
+}
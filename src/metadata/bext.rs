@@ -0,0 +1,150 @@
+use std::io::Cursor;
+use crate::utils::{ Loader, Saver };
+
+const BEXT_FIXED_SIZE    : usize = 602;
+const BEXT_TIME_REFERENCE: usize = 338;
+
+#[derive(Clone, Copy, PartialEq, Default)]
+pub struct Loudness {
+    pub value         : f32,
+    pub range         : f32,
+    pub max_true_peak : f32,
+    pub max_momentary : f32,
+    pub max_short_term: f32
+}
+
+#[derive(Clone, PartialEq, Default)]
+pub struct Bext {
+    pub description         : String           ,
+    pub originator          : String           ,
+    pub originator_reference: String           ,
+    pub origination_date    : String           ,
+    pub origination_time    : String           ,
+    pub time_reference      : u64              ,
+    pub version             : u16              ,
+    pub umid                : Option< [u8; 64] >,
+    pub loudness            : Option< Loudness >,
+    pub coding_history      : String
+}
+
+impl Bext {
+    pub fn from_bytes(bytes: &[u8]) -> Option< Self > {
+        if bytes.len() < BEXT_FIXED_SIZE {
+            return None
+        }
+
+        let mut loader = Loader::from(Cursor::new(bytes));
+
+        let description                   = loader.load_str(256)?;
+        let originator                    = loader.load_str( 32)?;
+        let originator_reference          = loader.load_str( 32)?;
+        let origination_date              = loader.load_str( 10)?;
+        let origination_time              = loader.load_str(  8)?;
+        let time_reference_low : u32      = loader.cload   (   )?;
+        let time_reference_high: u32      = loader.cload   (   )?;
+        let version            : u16      = loader.cload   (   )?;
+        let umid               : [u8; 64] = loader.cload   (   )?;
+        let loudness           : [i16; 5] = loader.cload   (   )?;
+        loader.seek(BEXT_FIXED_SIZE)?;
+        let coding_history                = loader.load_str(bytes.len() - BEXT_FIXED_SIZE)?;
+
+        let time_reference = (time_reference_high as u64) << 32 | time_reference_low as u64;
+        let umid           = if version >= 1 { Some(umid) } else { None };
+        let loudness       = if version >= 2 {
+            Some(Loudness {
+                value         : loudness[ 0 ] as f32 / 100.0,
+                range         : loudness[ 1 ] as f32 / 100.0,
+                max_true_peak : loudness[ 2 ] as f32 / 100.0,
+                max_momentary : loudness[ 3 ] as f32 / 100.0,
+                max_short_term: loudness[ 4 ] as f32 / 100.0
+            })
+        }
+        else {
+            None
+        };
+
+        Some(Self {
+            description         ,
+            originator          ,
+            originator_reference,
+            origination_date    ,
+            origination_time    ,
+            time_reference      ,
+            version             ,
+            umid                ,
+            loudness            ,
+            coding_history
+        })
+    }
+
+    pub fn time_reference_offset() -> usize {
+        BEXT_TIME_REFERENCE
+    }
+
+    pub fn to_bytes(&self) -> Option< Vec< u8 > > {
+        let mut saver = Saver::to(Cursor::new(Vec::new()));
+
+        let loudness = self.loudness.map(|loudness| [
+            (loudness.value          * 100.0).round() as i16,
+            (loudness.range          * 100.0).round() as i16,
+            (loudness.max_true_peak  * 100.0).round() as i16,
+            (loudness.max_momentary  * 100.0).round() as i16,
+            (loudness.max_short_term * 100.0).round() as i16
+        ]);
+
+        saver.save_str(&self.description         , 256)?;
+        saver.save_str(&self.originator          ,  32)?;
+        saver.save_str(&self.originator_reference,  32)?;
+        saver.save_str(&self.origination_date    ,  10)?;
+        saver.save_str(&self.origination_time    ,   8)?;
+        saver.save(&(self.time_reference as u32        ))?;
+        saver.save(&((self.time_reference >> 32) as u32))?;
+        saver.save(&self.version)?;
+        saver.save(&self.umid.unwrap_or([0u8; 64]))?;
+        saver.save(&loudness.unwrap_or([0i16; 5]))?;
+        saver.save(&[0u8; 180])?;
+        saver.save(self.coding_history.as_bytes())?;
+
+        Some(saver.into_inner().into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ utils::fixture::{ written, read }, common::{ Sample, FileFormat, ChunkPosition } };
+    use super::{ Bext, Loudness };
+
+    fn format() -> FileFormat {
+        FileFormat::new(Sample::I16, 1, 48000)
+    }
+
+    #[test]
+    fn round_trips() {
+        let loudness = Loudness { value: -23.0, range: 5.5, max_true_peak: -1.0, max_momentary: -20.25, max_short_term: -21.5 };
+        let bext     = Bext {
+            description         : "Scene 1".to_owned()                          ,
+            originator          : "Recorder".to_owned()                         ,
+            originator_reference: "REF0001".to_owned()                          ,
+            origination_date    : "2024-01-02".to_owned()                       ,
+            origination_time    : "03:04:05".to_owned()                         ,
+            time_reference      : 0x1_0000_0002                                 ,
+            version             : 2                                             ,
+            umid                : Some([7; 64])                                 ,
+            loudness            : Some(loudness)                                ,
+            coding_history      : "A=PCM,F=48000,W=16,M=mono\r\n".to_owned()
+        };
+        let file = written(format(), |writer| {
+            writer.add_bext(&bext).unwrap();
+            writer.write(&[0.0]).unwrap();
+        });
+        assert!(read(file).bext().unwrap() == bext);
+    }
+
+    #[test]
+    fn rejects_short_chunk() {
+        assert!(Bext::from_bytes(&[0; 601]).is_none());
+
+        let file = written(format(), |writer| writer.add_chunk(b"bext", &[0; 100], ChunkPosition::BeforeData).unwrap());
+        assert!(read(file).bext().is_none());
+    }
+}
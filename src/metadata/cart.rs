@@ -0,0 +1,199 @@
+use std::io::Cursor;
+use crate::utils::{ Loader, Saver };
+
+const CART_FIXED_SIZE: usize = 2048;
+const CART_NUM_TIMERS: usize =    8;
+const CART_VERSION   : &str  = "0101";
+
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub struct CartTimer {
+    pub usage: [u8; 4],
+    pub value: u32
+}
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct Cart {
+    pub version             : String                      ,
+    pub title               : String                      ,
+    pub artist              : String                      ,
+    pub cut_id              : String                      ,
+    pub client_id           : String                      ,
+    pub category            : String                      ,
+    pub classification      : String                      ,
+    pub out_cue             : String                      ,
+    pub start_date          : String                      ,
+    pub start_time          : String                      ,
+    pub end_date            : String                      ,
+    pub end_time            : String                      ,
+    pub producer_app_id     : String                      ,
+    pub producer_app_version: String                      ,
+    pub user_def            : String                      ,
+    pub level_reference     : i32                         ,
+    pub post_timers         : [CartTimer; CART_NUM_TIMERS],
+    pub url                 : String                      ,
+    pub tag_text            : String
+}
+
+impl Default for Cart {
+    fn default() -> Self {
+        Self {
+            version             : CART_VERSION.to_owned(),
+            title               : String::new()          ,
+            artist              : String::new()          ,
+            cut_id              : String::new()          ,
+            client_id           : String::new()          ,
+            category            : String::new()          ,
+            classification      : String::new()          ,
+            out_cue             : String::new()          ,
+            start_date          : String::new()          ,
+            start_time          : String::new()          ,
+            end_date            : String::new()          ,
+            end_time            : String::new()          ,
+            producer_app_id     : String::new()          ,
+            producer_app_version: String::new()          ,
+            user_def            : String::new()          ,
+            level_reference     : 0                      ,
+            post_timers         : Default::default()     ,
+            url                 : String::new()          ,
+            tag_text            : String::new()
+        }
+    }
+}
+
+impl CartTimer {
+    pub fn new(usage: &[u8; 4], value: u32) -> Self {
+        Self { usage: *usage, value }
+    }
+
+    pub fn is_used(&self) -> bool {
+        self.usage != [0u8; 4]
+    }
+}
+
+impl Cart {
+    pub fn from_bytes(bytes: &[u8]) -> Option< Self > {
+        if bytes.len() < CART_FIXED_SIZE {
+            return None
+        }
+
+        let mut loader = Loader::from(Cursor::new(bytes));
+
+        let version                   = loader.load_str( 4)?;
+        let title                     = loader.load_str(64)?;
+        let artist                    = loader.load_str(64)?;
+        let cut_id                    = loader.load_str(64)?;
+        let client_id                 = loader.load_str(64)?;
+        let category                  = loader.load_str(64)?;
+        let classification            = loader.load_str(64)?;
+        let out_cue                   = loader.load_str(64)?;
+        let start_date                = loader.load_str(10)?;
+        let start_time                = loader.load_str( 8)?;
+        let end_date                  = loader.load_str(10)?;
+        let end_time                  = loader.load_str( 8)?;
+        let producer_app_id           = loader.load_str(64)?;
+        let producer_app_version      = loader.load_str(64)?;
+        let user_def                  = loader.load_str(64)?;
+        let level_reference: i32      = loader.cload   (  )?;
+        let mut post_timers           = [CartTimer::default(); CART_NUM_TIMERS];
+
+        for timer in &mut post_timers {
+            timer.usage = loader.cload()?;
+            timer.value = loader.cload()?;
+        }
+
+        loader.skip(276)?;
+        let url                       = loader.load_str(1024)?;
+        let tag_text                  = loader.load_str(bytes.len() - CART_FIXED_SIZE)?;
+
+        Some(Self {
+            version             ,
+            title               ,
+            artist              ,
+            cut_id              ,
+            client_id           ,
+            category            ,
+            classification      ,
+            out_cue             ,
+            start_date          ,
+            start_time          ,
+            end_date            ,
+            end_time            ,
+            producer_app_id     ,
+            producer_app_version,
+            user_def            ,
+            level_reference     ,
+            post_timers         ,
+            url                 ,
+            tag_text
+        })
+    }
+
+    pub fn to_bytes(&self) -> Option< Vec< u8 > > {
+        let mut saver = Saver::to(Cursor::new(Vec::new()));
+
+        saver.save_str(&self.version             ,    4)?;
+        saver.save_str(&self.title               ,   64)?;
+        saver.save_str(&self.artist              ,   64)?;
+        saver.save_str(&self.cut_id              ,   64)?;
+        saver.save_str(&self.client_id           ,   64)?;
+        saver.save_str(&self.category            ,   64)?;
+        saver.save_str(&self.classification      ,   64)?;
+        saver.save_str(&self.out_cue             ,   64)?;
+        saver.save_str(&self.start_date          ,   10)?;
+        saver.save_str(&self.start_time          ,    8)?;
+        saver.save_str(&self.end_date            ,   10)?;
+        saver.save_str(&self.end_time            ,    8)?;
+        saver.save_str(&self.producer_app_id     ,   64)?;
+        saver.save_str(&self.producer_app_version,   64)?;
+        saver.save_str(&self.user_def            ,   64)?;
+        saver.save(&self.level_reference)?;
+
+        for timer in &self.post_timers {
+            saver.save(&timer.usage)?;
+            saver.save(&timer.value)?;
+        }
+
+        saver.save(&[0u8; 276])?;
+        saver.save_str(&self.url                 , 1024)?;
+        saver.save(self.tag_text.as_bytes())?;
+
+        Some(saver.into_inner().into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ utils::fixture::{ written, read }, common::{ Sample, FileFormat } };
+    use super::{ CartTimer, Cart };
+
+    #[test]
+    fn round_trips() {
+        let mut cart = Cart {
+            title          : "Spot".to_owned()                 ,
+            artist         : "Agency".to_owned()               ,
+            cut_id         : "C-42".to_owned()                 ,
+            start_date     : "2024-01-01".to_owned()           ,
+            level_reference: -32768                            ,
+            url            : "https://example.com".to_owned()  ,
+            tag_text       : "<tag>free text</tag>".to_owned() ,
+            ..Cart::default()
+        };
+        cart.post_timers[ 0 ] = CartTimer::new(b"SEC1", 48000);
+        cart.post_timers[ 1 ] = CartTimer::new(b"EOD ", 96000);
+
+        let file   = written(FileFormat::new(Sample::I16, 1, 48000), |writer| writer.add_cart(&cart).unwrap());
+        let loaded = read(file).cart().unwrap();
+        assert!(loaded == cart);
+        assert_eq!(loaded.post_timers.iter().filter(|timer| timer.is_used()).count(), 2);
+    }
+
+    #[test]
+    fn rejects_short_chunk() {
+        let bytes = Cart::default().to_bytes().unwrap();
+        assert_eq!(bytes.len(), 2048);
+        assert!(Cart::from_bytes(&bytes[ ..2047 ]).is_none());
+
+        let long = Cart { title: "x".repeat(100), ..Cart::default() };
+        assert_eq!(Cart::from_bytes(&long.to_bytes().unwrap()).unwrap().title, "x".repeat(64));
+    }
+}
@@ -0,0 +1,178 @@
+use super::list::{ parse_list, build_list, decode_text, encode_text };
+
+#[derive(Clone, PartialEq, Eq, Default)]
+pub struct CuePoint {
+    pub id      : u32             ,
+    pub position: u64             ,
+    pub length  : u64             ,
+    pub label   : Option< String >,
+    pub note    : Option< String >,
+    pub text    : Option< String >
+}
+
+#[derive(Clone, PartialEq, Eq, Default)]
+pub struct Cues {
+    points: Vec< CuePoint >
+}
+
+impl CuePoint {
+    pub fn marker(id: u32, position: u64) -> Self {
+        Self { id, position, ..Self::default() }
+    }
+
+    pub fn region(id: u32, position: u64, length: u64) -> Self {
+        Self { id, position, length, ..Self::default() }
+    }
+
+    pub fn is_region(&self) -> bool {
+        self.length > 0
+    }
+}
+
+impl Cues {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_bytes(cue: &[u8], adtl: Option< &[u8] >) -> Option< Self > {
+        let count = u32::from_le_bytes(cue.get(..4)?.try_into().ok()?) as usize;
+
+        if count > (cue.len() - 4) / 24 {
+            return None
+        }
+
+        let mut points = Vec::with_capacity(count);
+
+        for i in 0..count {
+            let record = cue.get(4 + i * 24..4 + (i + 1) * 24)?;
+            let field  = |n: usize| u32::from_le_bytes([record[ n ], record[ n + 1 ], record[ n + 2 ], record[ n + 3 ]]);
+            points.push(CuePoint::marker(field(0), field(20) as u64));
+        }
+
+        let mut cues = Self { points };
+
+        if let Some(adtl) = adtl {
+            let (list_type, items) = parse_list(adtl)?;
+
+            if &list_type != b"adtl" {
+                return None
+            }
+
+            for (id, data) in items {
+                if data.len() < 4 {
+                    continue
+                }
+
+                let cue_id = u32::from_le_bytes(data[ ..4 ].try_into().ok()?);
+                let Some(point) = cues.points.iter_mut().find(|point| point.id == cue_id) else {
+                    continue
+                };
+
+                match &id {
+                    b"labl"                     => point.label = Some(decode_text(&data[ 4.. ])),
+                    b"note"                     => point.note  = Some(decode_text(&data[ 4.. ])),
+                    b"ltxt" if data.len() >= 20 => {
+                        point.length = u32::from_le_bytes(data[ 4..8 ].try_into().ok()?) as u64;
+                        point.text   = Some(decode_text(&data[ 20.. ])).filter(|text| !text.is_empty());
+                    },
+                    _ => {}
+                }
+            }
+        }
+
+        Some(cues)
+    }
+
+    pub fn to_bytes(&self) -> Option< (Vec< u8 >, Option< Vec< u8 > >) > {
+        let mut cue   = u32::try_from(self.points.len()).ok()?.to_le_bytes().to_vec();
+        let mut items = Vec::new();
+
+        for (i, point) in self.points.iter().enumerate() {
+            cue.extend_from_slice(&point.id.to_le_bytes());
+            cue.extend_from_slice(&u32::try_from(i).ok()?.to_le_bytes());
+            cue.extend_from_slice(b"data");
+            cue.extend_from_slice(&0u32.to_le_bytes());
+            cue.extend_from_slice(&0u32.to_le_bytes());
+            cue.extend_from_slice(&u32::try_from(point.position).ok()?.to_le_bytes());
+
+            if let Some(label) = &point.label {
+                items.push((*b"labl", point.id.to_le_bytes().into_iter().chain(encode_text(label)).collect()));
+            }
+
+            if let Some(note) = &point.note {
+                items.push((*b"note", point.id.to_le_bytes().into_iter().chain(encode_text(note)).collect()));
+            }
+
+            if point.is_region() || point.text.is_some() {
+                let mut data = point.id.to_le_bytes().to_vec();
+                data.extend_from_slice(&u32::try_from(point.length).ok()?.to_le_bytes());
+                data.extend_from_slice(b"rgn ");
+                data.extend_from_slice(&[0u8; 8]);
+
+                if let Some(text) = &point.text {
+                    data.extend(encode_text(text));
+                }
+
+                items.push((*b"ltxt", data));
+            }
+        }
+
+        let adtl = if items.is_empty() { None } else { Some(build_list(b"adtl", &items)) };
+        Some((cue, adtl))
+    }
+
+    pub fn points(&self) -> &[CuePoint] {
+        &self.points
+    }
+
+    pub fn push(&mut self, point: CuePoint) {
+        self.points.push(point);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ utils::fixture::{ written, read, format } };
+    use super::{ CuePoint, Cues };
+
+    #[test]
+    fn round_trips() {
+        let mut cues = Cues::new();
+        cues.push(CuePoint { label: Some("Start".to_owned()), note: Some("take 2".to_owned()), ..CuePoint::marker(1, 0) });
+        cues.push(CuePoint { text: Some("Verse".to_owned()), label: Some("V".to_owned()), ..CuePoint::region(2, 100, 50) });
+        cues.push(CuePoint::marker(3, 4_000_000_000));
+
+        let file = written(format(), |writer| {
+            writer.write(&[0.0; 4]).unwrap();
+            writer.add_cues(&cues).unwrap();
+        });
+        let loaded = read(file).cues().unwrap();
+        assert!(loaded == cues);
+        assert!(loaded.points()[ 1 ].is_region());
+    }
+
+    #[test]
+    fn rejects_unrepresentable_and_truncated_cues() {
+        let mut cues = Cues::new();
+        cues.push(CuePoint::marker(1, u32::MAX as u64 + 1));
+        assert!(cues.to_bytes().is_none());
+        written(format(), |writer| assert!(writer.add_cues(&cues).is_none()));
+
+        let mut cues = Cues::new();
+        cues.push(CuePoint::region(1, 0, u32::MAX as u64 + 1));
+        assert!(cues.to_bytes().is_none());
+
+        let mut cue = 2u32.to_le_bytes().to_vec();
+        cue.extend_from_slice(&[0; 24]);
+        assert!(Cues::from_bytes(&cue, None).is_none());
+        assert!(Cues::from_bytes(&cue[ ..2 ], None).is_none());
+
+        cue[ ..4 ].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(Cues::from_bytes(&cue, None).is_none());
+        assert!(Cues::from_bytes(&[0; 4], Some(b"INFO")).is_none());
+    }
+}
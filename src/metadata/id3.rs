@@ -0,0 +1,363 @@
+#[derive(Clone, PartialEq, Eq)]
+pub enum Id3Frame {
+    Text    { id: [u8; 4], text: String                                            },
+    UserText{ description: String, text: String                                    },
+    Comment { language: [u8; 3], description: String, text: String                 },
+    Picture { mime: String, picture_type: u8, description: String, data: Vec< u8 > },
+    Raw     { id: [u8; 4], flags: [u8; 2], data: Vec< u8 >                         }
+}
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct Id3Tag {
+    version: u8             ,
+    frames : Vec< Id3Frame >
+}
+
+impl Default for Id3Tag {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Id3Frame {
+    pub fn id(&self) -> [u8; 4] {
+        match self {
+            Id3Frame::Text     { id, .. } => *id    ,
+            Id3Frame::UserText { ..     } => *b"TXXX",
+            Id3Frame::Comment  { ..     } => *b"COMM",
+            Id3Frame::Picture  { ..     } => *b"APIC",
+            Id3Frame::Raw      { id, .. } => *id
+        }
+    }
+
+    fn flags(&self) -> [u8; 2] {
+        match self {
+            Id3Frame::Raw { flags, .. } => *flags,
+            _                           => [0, 0]
+        }
+    }
+
+    fn parse(id: [u8; 4], flags: [u8; 2], data: &[u8]) -> Option< Self > {
+        match &id {
+            b"TXXX" => {
+                let (&encoding, rest)   = data.split_first()?;
+                let (description, rest) = split_text(encoding, rest);
+                Some(Id3Frame::UserText { description: decode(encoding, description), text: decode(encoding, rest) })
+            },
+            b"COMM" => {
+                let (&encoding, rest)   = data.split_first()?;
+                let language            = rest.get(..3)?.try_into().ok()?;
+                let (description, rest) = split_text(encoding, &rest[ 3.. ]);
+                Some(Id3Frame::Comment { language, description: decode(encoding, description), text: decode(encoding, rest) })
+            },
+            b"APIC" => {
+                let (&encoding, rest)     = data.split_first()?;
+                let (mime, rest)          = split_text(0, rest);
+                let (&picture_type, rest) = rest.split_first()?;
+                let (description, rest)   = split_text(encoding, rest);
+                Some(Id3Frame::Picture { mime: decode(0, mime), picture_type, description: decode(encoding, description), data: rest.to_vec() })
+            },
+            [b'T', ..] => {
+                let (&encoding, rest) = data.split_first()?;
+                Some(Id3Frame::Text { id, text: decode(encoding, rest) })
+            },
+            _ => Some(Id3Frame::Raw { id, flags, data: data.to_vec() })
+        }
+    }
+
+    fn to_bytes(&self, version: u8) -> Vec< u8 > {
+        let texts = match self {
+            Id3Frame::Text     { text, ..              } => vec![text.as_str()],
+            Id3Frame::UserText { description, text     } => vec![description.as_str(), text.as_str()],
+            Id3Frame::Comment  { description, text, .. } => vec![description.as_str(), text.as_str()],
+            Id3Frame::Picture  { description, ..       } => vec![description.as_str()],
+            Id3Frame::Raw      { data, ..              } => return data.clone()
+        };
+        let encoding = match version {
+            4                                                                      => 3,
+            _ if texts.iter().all(|text| text.chars().all(|c| (c as u32) < 0x100)) => 0,
+            _                                                                      => 1
+        };
+        let mut ret = vec![encoding];
+
+        match self {
+            Id3Frame::Text { text, .. } => {
+                ret.extend(encode(encoding, text));
+            },
+            Id3Frame::UserText { description, text } => {
+                ret.extend(encode(encoding, description));
+                ret.extend(terminator(encoding));
+                ret.extend(encode(encoding, text));
+            },
+            Id3Frame::Comment { language, description, text } => {
+                ret.extend_from_slice(language);
+                ret.extend(encode(encoding, description));
+                ret.extend(terminator(encoding));
+                ret.extend(encode(encoding, text));
+            },
+            Id3Frame::Picture { mime, picture_type, description, data } => {
+                ret.extend(encode(0, mime));
+                ret.push(0);
+                ret.push(*picture_type);
+                ret.extend(encode(encoding, description));
+                ret.extend(terminator(encoding));
+                ret.extend_from_slice(data);
+            },
+            Id3Frame::Raw { .. } => {}
+        }
+
+        ret
+    }
+}
+
+impl Id3Tag {
+    pub fn new() -> Self {
+        Self {
+            version: 3         ,
+            frames : Vec::new()
+        }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option< Self > {
+        if bytes.len() < 10 || &bytes[ ..3 ] != b"ID3" {
+            return None
+        }
+
+        let version = bytes[ 3 ];
+        let flags   = bytes[ 5 ];
+        let size    = syncsafe(&bytes[ 6..10 ]);
+        let body    = bytes.get(10..10 + size)?;
+
+        if !(3..=4).contains(&version) {
+            return None
+        }
+
+        let body = if flags & 0x80 != 0 { unsynchronise(body) } else { body.to_vec() };
+        let mut pos = 0;
+
+        if flags & 0x40 != 0 {
+            let extended = body.get(..4)?;
+            pos = match version {
+                3 => u32::from_be_bytes(extended.try_into().ok()?) as usize + 4,
+                _ => syncsafe(extended)
+            };
+        }
+
+        let mut frames = Vec::new();
+
+        while pos + 10 <= body.len() && body[ pos ] != 0 {
+            let id   : [u8; 4] = body[ pos..pos + 4 ].try_into().ok()?;
+            let size           = match version {
+                3 => u32::from_be_bytes(body[ pos + 4..pos + 8 ].try_into().ok()?) as usize,
+                _ => syncsafe(&body[ pos + 4..pos + 8 ])
+            };
+            let flags: [u8; 2] = body[ pos + 8..pos + 10 ].try_into().ok()?;
+            let encoded        = match version {
+                3 => flags[ 1 ] & 0xE0 != 0, // Compression, encryption, grouping
+                _ => flags[ 1 ] & 0x4F != 0  // Grouping, compression, encryption, unsynchronisation, data length
+            };
+            let data           = match body.get(pos + 10..pos + 10 + size) {
+                Some(data) => data,
+                None       => break
+            };
+
+            if encoded {
+                frames.push(Id3Frame::Raw { id, flags, data: data.to_vec() });
+            }
+            else if let Some(frame) = Id3Frame::parse(id, flags, data) {
+                frames.push(frame);
+            }
+
+            pos += 10 + size;
+        }
+
+        Some(Self { version, frames })
+    }
+
+    pub fn to_bytes(&self) -> Vec< u8 > {
+        let mut body = Vec::new();
+
+        for frame in &self.frames {
+            let data = frame.to_bytes(self.version);
+            body.extend_from_slice(&frame.id());
+            body.extend_from_slice(&match self.version {
+                4 => to_syncsafe(data.len()),
+                _ => (data.len() as u32).to_be_bytes()
+            });
+            body.extend_from_slice(&frame.flags());
+            body.extend(data);
+        }
+
+        let mut ret = b"ID3".to_vec();
+        ret.extend_from_slice(&[self.version, 0, 0]);
+        ret.extend_from_slice(&to_syncsafe(body.len()));
+        ret.extend(body);
+        ret
+    }
+
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    pub fn set_version(&mut self, version: u8) -> Option< () > {
+        if !(3..=4).contains(&version) {
+            return None
+        }
+
+        self.version = version;
+        Some(())
+    }
+
+    pub fn frames(&self) -> &[Id3Frame] {
+        &self.frames
+    }
+
+    pub fn push(&mut self, frame: Id3Frame) {
+        self.frames.push(frame);
+    }
+
+    pub fn text(&self, id: &[u8; 4]) -> Option< &str > {
+        self.frames.iter().find_map(|frame| match frame {
+            Id3Frame::Text { id: frame_id, text } if frame_id == id => Some(text.as_str()),
+            _                                                       => None
+        })
+    }
+
+    pub fn set_text(&mut self, id: &[u8; 4], text: &str) {
+        self.frames.retain(|frame| !matches!(frame, Id3Frame::Text { id: frame_id, .. } if frame_id == id));
+        self.frames.push(Id3Frame::Text { id: *id, text: text.to_owned() });
+    }
+
+    pub fn comments(&self) -> impl Iterator< Item = &Id3Frame > {
+        self.frames.iter().filter(|frame| matches!(frame, Id3Frame::Comment { .. }))
+    }
+
+    pub fn pictures(&self) -> impl Iterator< Item = &Id3Frame > {
+        self.frames.iter().filter(|frame| matches!(frame, Id3Frame::Picture { .. }))
+    }
+}
+
+fn syncsafe(bytes: &[u8]) -> usize {
+    bytes.iter().fold(0, |acc, &b| acc << 7 | (b & 0x7F) as usize)
+}
+
+fn to_syncsafe(n: usize) -> [u8; 4] {
+    [(n >> 21) as u8 & 0x7F, (n >> 14) as u8 & 0x7F, (n >> 7) as u8 & 0x7F, n as u8 & 0x7F]
+}
+
+fn unsynchronise(bytes: &[u8]) -> Vec< u8 > {
+    let mut ret = Vec::with_capacity(bytes.len());
+
+    for (i, &b) in bytes.iter().enumerate() {
+        if !(b == 0 && i > 0 && bytes[ i - 1 ] == 0xFF) {
+            ret.push(b);
+        }
+    }
+
+    ret
+}
+
+fn split_text(encoding: u8, bytes: &[u8]) -> (&[u8], &[u8]) {
+    let end = match encoding {
+        1 | 2 => (0..bytes.len() / 2).map(|i| i * 2).find(|&i| bytes[ i ] == 0 && bytes[ i + 1 ] == 0),
+        _     => bytes.iter().position(|&b| b == 0)
+    };
+
+    match end {
+        Some(end) => (&bytes[ ..end ], &bytes[ end + terminator(encoding).len().. ]),
+        None      => (bytes, &[])
+    }
+}
+
+fn terminator(encoding: u8) -> &'static [u8] {
+    match encoding {
+        1 | 2 => &[0, 0],
+        _     => &[0   ]
+    }
+}
+
+fn decode(encoding: u8, bytes: &[u8]) -> String {
+    let text = match encoding {
+        1 | 2 => {
+            let (big, bytes) = match bytes {
+                [0xFF, 0xFE, rest @ ..] => (false        , rest ),
+                [0xFE, 0xFF, rest @ ..] => (true         , rest ),
+                _                       => (encoding == 2, bytes)
+            };
+            let units = bytes.chunks_exact(2).map(|unit| match big {
+                true  => u16::from_be_bytes([unit[ 0 ], unit[ 1 ]]),
+                false => u16::from_le_bytes([unit[ 0 ], unit[ 1 ]])
+            });
+            char::decode_utf16(units).map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER)).collect::< String >()
+        },
+        3 => String::from_utf8_lossy(bytes).into_owned(),
+        _ => bytes.iter().map(|&c| c as char).collect()
+    };
+
+    text.trim_end_matches('\0').replace('\0', "/")
+}
+
+fn encode(encoding: u8, text: &str) -> Vec< u8 > {
+    match encoding {
+        1 => [0xFF, 0xFE].into_iter().chain(text.encode_utf16().flat_map(u16::to_le_bytes)).collect(),
+        3 => text.as_bytes().to_vec(),
+        _ => text.chars().map(|c| c as u32 as u8).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::fixture::{ written, read, format };
+    use super::{ Id3Frame, Id3Tag };
+
+    fn frame(id: &[u8; 4], flags: [u8; 2], data: &[u8]) -> Vec< u8 > {
+        let mut ret = id.to_vec();
+        ret.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        ret.extend_from_slice(&flags);
+        ret.extend_from_slice(data);
+        ret
+    }
+
+    fn tag(frames: &[Vec< u8 >]) -> Vec< u8 > {
+        let body    = frames.concat();
+        let mut ret = b"ID3\x03\x00\x00".to_vec();
+        ret.extend_from_slice(&[0, 0, (body.len() >> 7) as u8 & 0x7F, body.len() as u8 & 0x7F]);
+        ret.extend(body);
+        ret
+    }
+
+    #[test]
+    fn round_trips() {
+        for version in [3, 4] {
+            let mut id3 = Id3Tag::new();
+            id3.set_version(version).unwrap();
+            id3.set_text(b"TIT2", "Tïtle ✓");
+            id3.push(Id3Frame::UserText { description: "key".to_owned(), text: "value".to_owned() });
+            id3.push(Id3Frame::Comment  { language: *b"eng", description: String::new(), text: "note".to_owned() });
+            id3.push(Id3Frame::Picture  { mime: "image/png".to_owned(), picture_type: 3, description: "cover".to_owned(), data: vec![1, 2, 3] });
+            id3.push(Id3Frame::Raw      { id: *b"PRIV", flags: [0, 0], data: vec![9; 5] });
+
+            let file = written(format(), |writer| writer.add_id3(&id3).unwrap());
+            let loaded = read(file).id3().unwrap();
+            assert!(loaded == id3);
+            assert_eq!(loaded.text(b"TIT2"), Some("Tïtle ✓"));
+            assert_eq!(loaded.pictures().count(), 1);
+        }
+    }
+
+    #[test]
+    fn handles_malformed_frames() {
+        assert!(Id3Tag::from_bytes(b"ID3\x02\x00\x00\x00\x00\x00\x00").is_none());
+        assert!(Id3Tag::from_bytes(b"ID3\x03\x00\x00\x00\x00\x00\x10").is_none());
+
+        let compressed  = frame(b"TIT2", [0, 0x80], &[0, 0, 0, 4, 0x78, 0x9C]);
+        let mut overrun = frame(b"TPE1", [0, 0], b"\x00Artist");
+        overrun[ 4..8 ].copy_from_slice(&200u32.to_be_bytes());
+
+        let bytes = tag(&[frame(b"TXXX", [0, 0], &[]), compressed.clone(), frame(b"TALB", [0, 0], b"\x00Album"), overrun]);
+        let id3   = Id3Tag::from_bytes(&bytes).unwrap();
+
+        assert!(id3.frames() == [Id3Frame::Raw { id: *b"TIT2", flags: [0, 0x80], data: compressed[ 10.. ].to_vec() }, Id3Frame::Text { id: *b"TALB", text: "Album".to_owned() }]);
+        assert!(Id3Tag::from_bytes(&id3.to_bytes()).unwrap() == id3);
+    }
+}
@@ -0,0 +1,96 @@
+use super::list::{ parse_list, build_list, decode_text, encode_text };
+
+#[derive(Clone, PartialEq, Eq, Default)]
+pub struct Info {
+    tags: Vec< ([u8; 4], String) >
+}
+
+impl Info {
+    pub const TITLE    : [u8; 4] = *b"INAM";
+    pub const ARTIST   : [u8; 4] = *b"IART";
+    pub const ALBUM    : [u8; 4] = *b"IPRD";
+    pub const GENRE    : [u8; 4] = *b"IGNR";
+    pub const COMMENT  : [u8; 4] = *b"ICMT";
+    pub const COPYRIGHT: [u8; 4] = *b"ICOP";
+    pub const SOFTWARE : [u8; 4] = *b"ISFT";
+    pub const DATE     : [u8; 4] = *b"ICRD";
+    pub const TRACK    : [u8; 4] = *b"ITRK";
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option< Self > {
+        let (list_type, items) = parse_list(bytes)?;
+
+        if &list_type != b"INFO" {
+            return None
+        }
+
+        let tags = items.into_iter().map(|(id, data)| (id, decode_text(data))).collect();
+        Some(Self { tags })
+    }
+
+    pub fn to_bytes(&self) -> Vec< u8 > {
+        let items = self.tags.iter().map(|(id, text)| (*id, encode_text(text))).collect::< Vec< _ > >();
+        build_list(b"INFO", &items)
+    }
+
+    pub fn get(&self, id: &[u8; 4]) -> Option< &str > {
+        self.tags.iter().find(|(tag, _)| tag == id).map(|(_, text)| text.as_str())
+    }
+
+    pub fn set(&mut self, id: &[u8; 4], text: &str) {
+        match self.tags.iter_mut().find(|(tag, _)| tag == id) {
+            Some((_, value)) => *value = text.to_owned(),
+            None             => self.tags.push((*id, text.to_owned()))
+        }
+    }
+
+    pub fn remove(&mut self, id: &[u8; 4]) -> Option< String > {
+        let index = self.tags.iter().position(|(tag, _)| tag == id)?;
+        Some(self.tags.remove(index).1)
+    }
+
+    pub fn iter(&self) -> impl Iterator< Item = (&[u8; 4], &str) > {
+        self.tags.iter().map(|(id, text)| (id, text.as_str()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::fixture::{ written, read, format };
+    use super::Info;
+
+    #[test]
+    fn round_trips() {
+        let mut info = Info::new();
+        info.set(&Info::TITLE , "Ünïcode title");
+        info.set(&Info::ARTIST, "odd");
+        info.set(&Info::TRACK , "3");
+        info.set(&Info::ARTIST, "Artist");
+        assert_eq!(info.remove(&Info::TRACK).as_deref(), Some("3"));
+
+        let file = written(format(), |writer| {
+            writer.write(&[0.0]).unwrap();
+            writer.add_info(&info).unwrap();
+        });
+        let tags = read(file).info().unwrap();
+        assert!(tags == info);
+        assert_eq!(tags.get(&Info::TITLE), Some("Ünïcode title"));
+        assert_eq!(tags.iter().map(|(id, _)| *id).collect::< Vec< _ > >(), [Info::TITLE, Info::ARTIST]);
+    }
+
+    #[test]
+    fn handles_malformed_lists() {
+        assert!(Info::from_bytes(b"INF").is_none());
+        assert!(Info::from_bytes(b"adtlINAM\x02\x00\x00\x00a\x00").is_none());
+
+        let info = Info::from_bytes(b"INFOINAM\x10\x00\x00\x00Short\xE9").unwrap();
+        assert_eq!(info.get(&Info::TITLE), Some("Shorté"));
+    }
+}
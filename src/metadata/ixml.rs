@@ -0,0 +1,190 @@
+#[derive(Clone, PartialEq, Eq, Default)]
+pub struct IxmlTrack {
+    pub channel_index   : u32   ,
+    pub interleave_index: u32   ,
+    pub name            : String,
+    pub function        : String
+}
+
+#[derive(Clone, PartialEq, Eq, Default)]
+pub struct IxmlSpeed {
+    pub master_speed          : String       ,
+    pub timecode_rate         : String       ,
+    pub timecode_flag         : String       ,
+    pub samples_since_midnight: Option< u64 >,
+    pub timestamp_sample_rate : Option< u32 >
+}
+
+#[derive(Clone, PartialEq, Eq, Default)]
+pub struct Ixml {
+    pub project: String          ,
+    pub scene  : String          ,
+    pub take   : String          ,
+    pub tape   : String          ,
+    pub note   : String          ,
+    pub tracks : Vec< IxmlTrack >,
+    pub speed  : IxmlSpeed
+}
+
+impl Ixml {
+    pub fn from_xml(xml: &str) -> Option< Self > {
+        let root   = element(xml, "BWFXML")?;
+        let tracks = element(root, "TRACK_LIST").map(|list| elements(list, "TRACK").map(|track| IxmlTrack {
+            channel_index   : text(track, "CHANNEL_INDEX"   ).parse().unwrap_or(0),
+            interleave_index: text(track, "INTERLEAVE_INDEX").parse().unwrap_or(0),
+            name            : text(track, "NAME"            ),
+            function        : text(track, "FUNCTION"        )
+        }).collect()).unwrap_or_default();
+        let speed  = element(root, "SPEED").map(|speed| {
+            let hi = text(speed, "TIMESTAMP_SAMPLES_SINCE_MIDNIGHT_HI").parse::< u64 >().ok();
+            let lo = text(speed, "TIMESTAMP_SAMPLES_SINCE_MIDNIGHT_LO").parse::< u64 >().ok();
+
+            IxmlSpeed {
+                master_speed          : text(speed, "MASTER_SPEED" ),
+                timecode_rate         : text(speed, "TIMECODE_RATE"),
+                timecode_flag         : text(speed, "TIMECODE_FLAG"),
+                samples_since_midnight: lo.map(|lo| hi.unwrap_or(0) << 32 | lo),
+                timestamp_sample_rate : text(speed, "TIMESTAMP_SAMPLE_RATE").parse().ok()
+            }
+        }).unwrap_or_default();
+
+        Some(Self {
+            project: text(root, "PROJECT"),
+            scene  : text(root, "SCENE"  ),
+            take   : text(root, "TAKE"   ),
+            tape   : text(root, "TAPE"   ),
+            note   : text(root, "NOTE"   ),
+            tracks ,
+            speed
+        })
+    }
+
+    pub fn to_xml(&self) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<BWFXML>\n<IXML_VERSION>2.10</IXML_VERSION>\n");
+
+        push(&mut xml, "PROJECT", &self.project);
+        push(&mut xml, "SCENE"  , &self.scene  );
+        push(&mut xml, "TAKE"   , &self.take   );
+        push(&mut xml, "TAPE"   , &self.tape   );
+        push(&mut xml, "NOTE"   , &self.note   );
+
+        xml.push_str("<SPEED>\n");
+        push(&mut xml, "MASTER_SPEED" , &self.speed.master_speed );
+        push(&mut xml, "TIMECODE_RATE", &self.speed.timecode_rate);
+        push(&mut xml, "TIMECODE_FLAG", &self.speed.timecode_flag);
+
+        if let Some(samples) = self.speed.samples_since_midnight {
+            push(&mut xml, "TIMESTAMP_SAMPLES_SINCE_MIDNIGHT_HI", &(samples >> 32        ).to_string());
+            push(&mut xml, "TIMESTAMP_SAMPLES_SINCE_MIDNIGHT_LO", &(samples & 0xFFFFFFFF).to_string());
+        }
+
+        if let Some(sample_rate) = self.speed.timestamp_sample_rate {
+            push(&mut xml, "TIMESTAMP_SAMPLE_RATE", &sample_rate.to_string());
+        }
+
+        xml.push_str("</SPEED>\n");
+
+        if !self.tracks.is_empty() {
+            xml.push_str("<TRACK_LIST>\n");
+            push(&mut xml, "TRACK_COUNT", &self.tracks.len().to_string());
+
+            for track in &self.tracks {
+                xml.push_str("<TRACK>\n");
+                push(&mut xml, "CHANNEL_INDEX"   , &track.channel_index   .to_string());
+                push(&mut xml, "INTERLEAVE_INDEX", &track.interleave_index.to_string());
+                push(&mut xml, "NAME"            , &track.name                        );
+                push(&mut xml, "FUNCTION"        , &track.function                    );
+                xml.push_str("</TRACK>\n");
+            }
+
+            xml.push_str("</TRACK_LIST>\n");
+        }
+
+        xml.push_str("</BWFXML>\n");
+        xml
+    }
+}
+
+fn element< 'a >(xml: &'a str, tag: &str) -> Option< &'a str > {
+    elements(xml, tag).next()
+}
+
+fn elements< 'a >(xml: &'a str, tag: &str) -> impl Iterator< Item = &'a str > {
+    let open  = format!("<{}>" , tag);
+    let close = format!("</{}>", tag);
+    let mut rest = xml;
+
+    std::iter::from_fn(move || {
+        let begin = rest.find(&open)? + open.len();
+        let end   = begin + rest[ begin.. ].find(&close)?;
+        let inner = &rest[ begin..end ];
+        rest      = &rest[ end + close.len().. ];
+        Some(inner)
+    })
+}
+
+fn text(xml: &str, tag: &str) -> String {
+    element(xml, tag).map(|inner| unescape(inner.trim())).unwrap_or_default()
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;"  , "<" )
+        .replace("&gt;"  , ">" )
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'" )
+        .replace("&amp;" , "&" )
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&' , "&amp;" )
+        .replace('<' , "&lt;"  )
+        .replace('>' , "&gt;"  )
+        .replace('"' , "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn push(xml: &mut String, tag: &str, text: &str) {
+    if !text.is_empty() {
+        xml.push_str(&format!("<{}>{}</{}>\n", tag, escape(text), tag));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ utils::fixture::{ written, read }, common::{ Sample, FileFormat } };
+    use super::{ IxmlTrack, IxmlSpeed, Ixml };
+
+    #[test]
+    fn round_trips() {
+        let tracks = vec![
+            IxmlTrack { channel_index: 1, interleave_index: 1, name: "Boom".to_owned(), function: "MS-MID".to_owned() },
+            IxmlTrack { channel_index: 2, interleave_index: 2, name: "Lav" .to_owned(), function: String::new()       }
+        ];
+        let speed  = IxmlSpeed { timecode_rate: "25/1".to_owned(), samples_since_midnight: Some(0x1_2345_6789), timestamp_sample_rate: Some(48000), ..IxmlSpeed::default() };
+        let ixml   = Ixml {
+            project: "Feature & <Co>".to_owned(),
+            scene  : "12A".to_owned()           ,
+            take   : "3".to_owned()             ,
+            note   : "it's \"good\"".to_owned() ,
+            tracks                              ,
+            speed                               ,
+            ..Ixml::default()
+        };
+        let file       = written(FileFormat::new(Sample::I16, 2, 48000), |writer| writer.add_ixml(&ixml).unwrap());
+        let mut reader = read(file);
+        assert!(reader.ixml().unwrap() == ixml);
+        assert!(reader.ixml_raw().unwrap().contains("<PROJECT>Feature &amp; &lt;Co&gt;</PROJECT>"));
+    }
+
+    #[test]
+    fn handles_malformed_xml() {
+        assert!(Ixml::from_xml("").is_none());
+        assert!(Ixml::from_xml("<BWFXML><PROJECT>x</PROJECT>").is_none());
+
+        let ixml = Ixml::from_xml("<BWFXML><SCENE> 1 </SCENE><TAKE>2<TRACK_LIST><TRACK><CHANNEL_INDEX>x</CHANNEL_INDEX></TRACK></TRACK_LIST></BWFXML>").unwrap();
+        assert_eq!(ixml.scene, "1");
+        assert_eq!(ixml.take , "");
+        assert_eq!(ixml.tracks.len(), 1);
+        assert_eq!(ixml.tracks[ 0 ].channel_index, 0);
+    }
+}
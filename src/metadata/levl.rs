@@ -0,0 +1,215 @@
+use std::io::Cursor;
+use crate::utils::{ Loader, Saver };
+
+const LEVL_HEADER_SIZE: usize = 120;
+const LEVL_VERSION    : u32   =   1;
+const FORMAT_BYTE     : u32   =   1;
+const FORMAT_WORD     : u32   =   2;
+const UNKNOWN_POSITION: u32   = 0xFFFFFFFF;
+
+#[derive(Clone, PartialEq)]
+pub struct PeakEnvelope {
+    block_size   : usize        ,
+    num_channels : usize        ,
+    peaks        : Vec< f32 >   ,
+    peak_value   : f32          ,
+    peak_position: Option< u64 >,
+    timestamp    : String
+}
+
+impl PeakEnvelope {
+    pub fn new(num_channels: usize, block_size: usize) -> Option< Self > {
+        if num_channels == 0 || block_size == 0 || u32::try_from(block_size).is_err() {
+            return None
+        }
+
+        Some(Self {
+            block_size                ,
+            num_channels              ,
+            peaks        : Vec::new() ,
+            peak_value   : 0.0        ,
+            peak_position: None       ,
+            timestamp    : String::new()
+        })
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option< Self > {
+        if bytes.len() < LEVL_HEADER_SIZE {
+            return None
+        }
+
+        let mut loader = Loader::from(Cursor::new(bytes));
+
+        let _version         : u32 = loader.cload()?;
+        let format           : u32 = loader.cload()?;
+        let points_per_value : u32 = loader.cload()?;
+        let block_size       : u32 = loader.cload()?;
+        let num_channels     : u32 = loader.cload()?;
+        let num_peak_frames  : u32 = loader.cload()?;
+        let peak_position    : u32 = loader.cload()?;
+        let offset_to_peaks  : u32 = loader.cload()?;
+        let timestamp              = loader.load_str(28)?;
+
+        let (width, scale) = match format {
+            FORMAT_BYTE => (1, u8 ::MAX as f32),
+            FORMAT_WORD => (2, u16::MAX as f32),
+            _           => return None
+        };
+
+        if !(1..=2).contains(&points_per_value) {
+            return None
+        }
+
+        let mut envelope = Self::new(num_channels as usize, block_size as usize)?;
+        let begin        = (offset_to_peaks as usize).checked_sub(8)?;
+        let count        = num_peak_frames as usize * envelope.num_channels;
+        let stride       = width * points_per_value as usize;
+        let data         = bytes.get(begin..begin + count * stride)?;
+
+        envelope.peaks = data.chunks_exact(stride).map(|value| {
+            value.chunks_exact(width).map(|point| match width {
+                2 => u16::from_le_bytes([point[ 0 ], point[ 1 ]]) as f32,
+                _ => point[ 0 ] as f32
+            }).fold(0f32, f32::max) / scale
+        }).collect();
+        envelope.peak_value    = envelope.peaks.iter().copied().fold(0f32, f32::max);
+        envelope.peak_position = if peak_position == UNKNOWN_POSITION { None } else { Some(peak_position as u64) };
+        envelope.timestamp     = timestamp;
+
+        Some(envelope)
+    }
+
+    pub fn to_bytes(&self) -> Option< Vec< u8 > > {
+        let mut saver = Saver::to(Cursor::new(Vec::new()));
+        let position  = self.peak_position.and_then(|position| u32::try_from(position).ok()).unwrap_or(UNKNOWN_POSITION);
+
+        saver.save    (&LEVL_VERSION                    )?;
+        saver.save    (&FORMAT_WORD                     )?;
+        saver.save    (&1u32                            )?; // Points per value
+        saver.save    (&(self.block_size   as u32)      )?;
+        saver.save    (&(self.num_channels as u32)      )?;
+        saver.save    (&u32::try_from(self.len()).ok()? )?;
+        saver.save    (&position                        )?;
+        saver.save    (&(LEVL_HEADER_SIZE as u32 + 8)   )?;
+        saver.save_str(&self.timestamp, 28              )?;
+        saver.save    (&[0u8; 60]                       )?;
+
+        for peak in &self.peaks {
+            saver.save(&((peak.clamp(0.0, 1.0) * u16::MAX as f32) as u16))?;
+        }
+
+        Some(saver.into_inner().into_inner())
+    }
+
+    pub(crate) fn accumulate(&mut self, first: usize, samples: &[f32]) {
+        for (i, x) in samples.iter().enumerate() {
+            let index   = first + i;
+            let frame   = index / self.num_channels;
+            let channel = index % self.num_channels;
+            let slot    = frame / self.block_size * self.num_channels + channel;
+            let value   = x.abs();
+
+            if slot >= self.peaks.len() {
+                self.peaks.resize(slot / self.num_channels * self.num_channels + self.num_channels, 0.0);
+            }
+
+            self.peaks[ slot ] = self.peaks[ slot ].max(value);
+
+            if value > self.peak_value {
+                self.peak_value    = value;
+                self.peak_position = Some(frame as u64);
+            }
+        }
+    }
+
+    pub fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    pub fn num_channels(&self) -> usize {
+        self.num_channels
+    }
+
+    pub fn len(&self) -> usize {
+        self.peaks.len() / self.num_channels
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.peaks.is_empty()
+    }
+
+    pub fn channel(&self, channel: usize) -> Option< Vec< f32 > > {
+        if channel >= self.num_channels {
+            return None
+        }
+
+        Some(self.peaks.iter().skip(channel).step_by(self.num_channels).copied().collect())
+    }
+
+    pub fn channels(&self) -> Vec< Vec< f32 > > {
+        (0..self.num_channels).filter_map(|channel| self.channel(channel)).collect()
+    }
+
+    pub fn peak_position(&self) -> Option< u64 > {
+        self.peak_position
+    }
+
+    pub fn timestamp(&self) -> &str {
+        &self.timestamp
+    }
+
+    pub fn set_timestamp(&mut self, timestamp: &str) {
+        self.timestamp = timestamp.to_owned();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ utils::fixture::{ written, read }, common::{ Sample, FileFormat } };
+    use super::PeakEnvelope;
+
+    fn close(a: &[f32], b: &[f32]) -> bool {
+        a.len() == b.len() && a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-4)
+    }
+
+    #[test]
+    fn round_trips() {
+        let file = written(FileFormat::new(Sample::F32, 2, 8000), |writer| {
+            writer.enable_peak_envelope(2).unwrap();
+            writer.write(&[0.5, -0.25, -1.0, 0.0, 0.25, 0.125]).unwrap();
+        });
+        let mut reader = read(file);
+        let chunk      = reader.find_chunk(b"levl").unwrap();
+        let bytes      = reader.read_chunk(&chunk).unwrap();
+        assert_eq!(&bytes[ 4..8 ], &2u32.to_le_bytes());
+
+        let envelope = reader.peak_envelope().unwrap();
+        assert_eq!((envelope.block_size(), envelope.num_channels(), envelope.len()), (2, 2, 2));
+        assert!(close(&envelope.channel(0).unwrap(), &[1.0 , 0.25 ]));
+        assert!(close(&envelope.channel(1).unwrap(), &[0.25, 0.125]));
+        assert_eq!(envelope.peak_position(), Some(1));
+        assert!(envelope.channel(2).is_none());
+    }
+
+    #[test]
+    fn rejects_malformed_chunks() {
+        assert!(PeakEnvelope::new(0, 256).is_none());
+        assert!(PeakEnvelope::new(1, 0  ).is_none());
+
+        let mut envelope = PeakEnvelope::new(1, 4).unwrap();
+        envelope.accumulate(0, &[1.0, -1.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0]);
+        envelope.set_timestamp("2024:01:02:03:04:05:00");
+        let bytes = envelope.to_bytes().unwrap();
+        assert!(PeakEnvelope::from_bytes(&bytes).unwrap() == envelope);
+        assert!(PeakEnvelope::from_bytes(&bytes[ ..bytes.len() - 1 ]).is_none());
+        assert!(PeakEnvelope::from_bytes(&bytes[ ..119 ]).is_none());
+
+        let mut bytes = bytes;
+        bytes[ 4 ] = 3;
+        assert!(PeakEnvelope::from_bytes(&bytes).is_none());
+
+        bytes[ 4 ] = 1;
+        bytes[ 8 ] = 0;
+        assert!(PeakEnvelope::from_bytes(&bytes).is_none());
+    }
+}
@@ -0,0 +1,55 @@
+pub type SubChunk< 'a > = ([u8; 4], &'a [u8]);
+
+pub fn parse_list(bytes: &[u8]) -> Option< ([u8; 4], Vec< SubChunk< '_ > >) > {
+    let list_type: [u8; 4] = bytes.get(..4)?.try_into().ok()?;
+    let mut items          = Vec::new();
+    let mut pos            = 4;
+
+    while pos + 8 <= bytes.len() {
+        let id  : [u8; 4] = bytes[ pos..pos + 4 ].try_into().ok()?;
+        let size          = u32::from_le_bytes(bytes[ pos + 4..pos + 8 ].try_into().ok()?) as usize;
+        let begin         = pos + 8;
+        let end           = (begin + size).min(bytes.len());
+        items.push((id, &bytes[ begin..end ]));
+
+        pos = end;
+
+        if size % 2 == 1 && bytes.get(end) == Some(&0) {
+            pos += 1;
+        }
+    }
+
+    Some((list_type, items))
+}
+
+pub fn build_list(list_type: &[u8; 4], items: &[([u8; 4], Vec< u8 >)]) -> Vec< u8 > {
+    let mut ret = list_type.to_vec();
+
+    for (id, data) in items {
+        ret.extend_from_slice(id);
+        ret.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        ret.extend_from_slice(data);
+
+        if data.len() % 2 == 1 {
+            ret.push(0);
+        }
+    }
+
+    ret
+}
+
+pub fn decode_text(bytes: &[u8]) -> String {
+    let len   = bytes.iter().position(|&c| c == 0).unwrap_or(bytes.len());
+    let bytes = &bytes[ ..len ];
+
+    match std::str::from_utf8(bytes) {
+        Ok (text) => text.to_owned(),
+        Err(_   ) => bytes.iter().map(|&c| c as char).collect()
+    }
+}
+
+pub fn encode_text(text: &str) -> Vec< u8 > {
+    let mut ret = text.as_bytes().to_vec();
+    ret.push(0);
+    ret
+}
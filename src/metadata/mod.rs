@@ -0,0 +1,25 @@
+mod list;
+mod bext;
+mod timecode;
+mod info;
+mod id3;
+mod cue;
+mod sampler;
+mod acid;
+mod ixml;
+mod levl;
+mod cart;
+mod peak;
+
+pub use bext::{ Loudness, Bext };
+pub use timecode::{ FrameRate, Timecode };
+pub use info::Info;
+pub use id3::{ Id3Frame, Id3Tag };
+pub use cue::{ CuePoint, Cues };
+pub use sampler::{ LoopType, SampleLoop, Sampler, Instrument };
+pub use acid::Acid;
+pub use ixml::{ IxmlTrack, IxmlSpeed, Ixml };
+pub use levl::PeakEnvelope;
+pub use cart::{ CartTimer, Cart };
+pub use peak::{ ChannelPeak, Peak };
+pub(crate) use list::parse_list;
@@ -0,0 +1,123 @@
+use std::{ io::Cursor, time::{ SystemTime, UNIX_EPOCH } };
+use crate::utils::{ Loader, Saver };
+
+const PEAK_HEADER_SIZE : usize = 8;
+const PEAK_CHANNEL_SIZE: usize = 8;
+const PEAK_VERSION     : u32   = 1;
+
+#[derive(Clone, Copy, PartialEq, Default)]
+pub struct ChannelPeak {
+    pub value   : f32,
+    pub position: u32
+}
+
+#[derive(Clone, PartialEq)]
+pub struct Peak {
+    pub timestamp: u32               ,
+    pub channels : Vec< ChannelPeak >
+}
+
+impl Peak {
+    pub fn new(num_channels: usize) -> Self {
+        Self {
+            timestamp: 0                                      ,
+            channels : vec![ChannelPeak::default(); num_channels]
+        }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option< Self > {
+        if bytes.len() < PEAK_HEADER_SIZE {
+            return None
+        }
+
+        let mut loader = Loader::from(Cursor::new(bytes));
+
+        let _version : u32 = loader.cload()?;
+        let timestamp: u32 = loader.cload()?;
+        let channels       = (0..(bytes.len() - PEAK_HEADER_SIZE) / PEAK_CHANNEL_SIZE).map(|_| {
+            let value   : f32 = loader.cload()?;
+            let position: u32 = loader.cload()?;
+            Some(ChannelPeak { value, position })
+        }).collect::< Option< Vec< _ > > >()?;
+
+        Some(Self { timestamp, channels })
+    }
+
+    pub fn to_bytes(&self) -> Option< Vec< u8 > > {
+        let mut saver = Saver::to(Cursor::new(Vec::new()));
+
+        saver.save(&PEAK_VERSION  )?;
+        saver.save(&self.timestamp)?;
+
+        for channel in &self.channels {
+            saver.save(&channel.value   )?;
+            saver.save(&channel.position)?;
+        }
+
+        Some(saver.into_inner().into_inner())
+    }
+
+    pub(crate) fn accumulate(&mut self, first: usize, samples: &[f32]) {
+        let num_channels = self.channels.len();
+
+        if num_channels == 0 {
+            return
+        }
+
+        for (i, x) in samples.iter().enumerate() {
+            let index   = first + i;
+            let channel = &mut self.channels[ index % num_channels ];
+
+            if x.abs() > channel.value {
+                channel.value    = x.abs();
+                channel.position = u32::try_from(index / num_channels).unwrap_or(u32::MAX);
+            }
+        }
+    }
+
+    pub(crate) fn stamp(&mut self) {
+        self.timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map(|now| now.as_secs() as u32).unwrap_or(0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ utils::fixture::{ written, read }, common::{ Sample, FileFormat } };
+    use super::{ ChannelPeak, Peak };
+
+    #[test]
+    fn round_trips() {
+        let file = written(FileFormat::new(Sample::F32, 2, 8000), |writer| {
+            writer.enable_peak().unwrap();
+            writer.write(&[0.5, -0.25, -0.75, 0.125]).unwrap();
+            writer.write(&[0.0, 1.0]).unwrap();
+        });
+        let peak = read(file).peak().unwrap();
+        assert!(peak.channels == [ChannelPeak { value: 0.75, position: 1 }, ChannelPeak { value: 1.0, position: 2 }]);
+        assert!(peak.timestamp > 0);
+
+        let peak = Peak { timestamp: 7, channels: vec![ChannelPeak { value: 0.5, position: 3 }] };
+        assert!(Peak::from_bytes(&peak.to_bytes().unwrap()).unwrap() == peak);
+    }
+
+    #[test]
+    fn handles_malformed_chunks() {
+        assert!(Peak::from_bytes(&[0; 7]).is_none());
+        assert!(Peak::from_bytes(&[0; 8]).unwrap().channels.is_empty());
+        assert_eq!(Peak::from_bytes(&[0; 20]).unwrap().channels.len(), 1);
+
+        written(FileFormat::new(Sample::F32, 1, 8000), |writer| {
+            writer.write(&[0.0]).unwrap();
+            assert!(writer.enable_peak().is_none());
+        });
+        written(FileFormat::new(Sample::F32, 0, 8000), |writer| assert!(writer.enable_peak().is_none()));
+
+        let mut peak = Peak::new(0);
+        peak.accumulate(0, &[1.0]);
+        assert!(peak.channels.is_empty());
+
+        let mut peak = Peak::new(2);
+        peak.accumulate(u32::MAX as usize * 2 + 2, &[0.5, 0.25]);
+        assert!(peak.channels == [ChannelPeak { value: 0.5, position: u32::MAX }, ChannelPeak { value: 0.25, position: u32::MAX }]);
+    }
+}
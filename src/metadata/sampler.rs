@@ -0,0 +1,242 @@
+use std::io::Cursor;
+use crate::utils::{ Loader, Saver };
+
+const SMPL_FIXED_SIZE: usize = 36;
+const SMPL_LOOP_SIZE : usize = 24;
+const INST_SIZE      : usize =  7;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum LoopType {
+    Forward   ,
+    PingPong  ,
+    Backward  ,
+    Other(u32)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct SampleLoop {
+    pub cue_id    : u32     ,
+    pub loop_type : LoopType,
+    pub start     : u32     ,
+    pub end       : u32     ,
+    pub fraction  : u32     ,
+    pub play_count: u32
+}
+
+#[derive(Clone, PartialEq, Eq, Default)]
+pub struct Sampler {
+    pub manufacturer       : u32              ,
+    pub product            : u32              ,
+    pub sample_period      : u32              ,
+    pub midi_unity_note    : u32              ,
+    pub midi_pitch_fraction: u32              ,
+    pub smpte_format       : u32              ,
+    pub smpte_offset       : u32              ,
+    pub loops              : Vec< SampleLoop >,
+    pub sampler_data       : Vec< u8 >
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Instrument {
+    pub base_note    : u8,
+    pub detune       : i8,
+    pub gain         : i8,
+    pub low_note     : u8,
+    pub high_note    : u8,
+    pub low_velocity : u8,
+    pub high_velocity: u8
+}
+
+impl LoopType {
+    fn new(n: u32) -> Self {
+        match n {
+            0 => LoopType::Forward ,
+            1 => LoopType::PingPong,
+            2 => LoopType::Backward,
+            _ => LoopType::Other(n)
+        }
+    }
+
+    fn value(&self) -> u32 {
+        match self {
+            LoopType::Forward  => 0,
+            LoopType::PingPong => 1,
+            LoopType::Backward => 2,
+            LoopType::Other(n) => *n
+        }
+    }
+}
+
+impl SampleLoop {
+    pub fn new(start: u32, end: u32) -> Self {
+        Self {
+            cue_id    : 0                ,
+            loop_type : LoopType::Forward,
+            start                        ,
+            end                          ,
+            fraction  : 0                ,
+            play_count: 0
+        }
+    }
+}
+
+impl Sampler {
+    pub fn from_bytes(bytes: &[u8]) -> Option< Self > {
+        if bytes.len() < SMPL_FIXED_SIZE {
+            return None
+        }
+
+        let mut loader = Loader::from(Cursor::new(bytes));
+
+        let manufacturer       : u32 = loader.cload()?;
+        let product            : u32 = loader.cload()?;
+        let sample_period      : u32 = loader.cload()?;
+        let midi_unity_note    : u32 = loader.cload()?;
+        let midi_pitch_fraction: u32 = loader.cload()?;
+        let smpte_format       : u32 = loader.cload()?;
+        let smpte_offset       : u32 = loader.cload()?;
+        let num_loops          : u32 = loader.cload()?;
+        let sampler_data_size  : u32 = loader.cload()?;
+
+        if bytes.len() < SMPL_FIXED_SIZE + num_loops as usize * SMPL_LOOP_SIZE {
+            return None
+        }
+
+        let mut loops = Vec::with_capacity(num_loops as usize);
+
+        for _ in 0..num_loops {
+            let [cue_id, loop_type, start, end, fraction, play_count]: [u32; 6] = loader.cload()?;
+            loops.push(SampleLoop { cue_id, loop_type: LoopType::new(loop_type), start, end, fraction, play_count });
+        }
+
+        let rest         = &bytes[ loader.pos().. ];
+        let sampler_data = rest[ ..rest.len().min(sampler_data_size as usize) ].to_vec();
+
+        Some(Self {
+            manufacturer       ,
+            product            ,
+            sample_period      ,
+            midi_unity_note    ,
+            midi_pitch_fraction,
+            smpte_format       ,
+            smpte_offset       ,
+            loops              ,
+            sampler_data
+        })
+    }
+
+    pub fn to_bytes(&self) -> Option< Vec< u8 > > {
+        let mut saver = Saver::to(Cursor::new(Vec::new()));
+
+        saver.save(& self.manufacturer              )?;
+        saver.save(& self.product                   )?;
+        saver.save(& self.sample_period             )?;
+        saver.save(& self.midi_unity_note           )?;
+        saver.save(& self.midi_pitch_fraction       )?;
+        saver.save(& self.smpte_format              )?;
+        saver.save(& self.smpte_offset              )?;
+        saver.save(&(self.loops       .len() as u32))?;
+        saver.save(&(self.sampler_data.len() as u32))?;
+
+        for l in &self.loops {
+            saver.save(&[l.cue_id, l.loop_type.value(), l.start, l.end, l.fraction, l.play_count])?;
+        }
+
+        saver.save(self.sampler_data.as_slice())?;
+        Some(saver.into_inner().into_inner())
+    }
+
+    pub fn with_sample_rate(sample_rate: u32) -> Self {
+        Self {
+            sample_period  : 1_000_000_000u64.checked_div(sample_rate as u64).unwrap_or(0) as u32,
+            midi_unity_note: 60                                                                  ,
+            ..Self::default()
+        }
+    }
+}
+
+impl Default for Instrument {
+    fn default() -> Self {
+        Self {
+            base_note    : 60 ,
+            detune       : 0  ,
+            gain         : 0  ,
+            low_note     : 0  ,
+            high_note    : 127,
+            low_velocity : 1  ,
+            high_velocity: 127
+        }
+    }
+}
+
+impl Instrument {
+    pub fn from_bytes(bytes: &[u8]) -> Option< Self > {
+        let bytes: [u8; INST_SIZE] = bytes.get(..INST_SIZE)?.try_into().ok()?;
+
+        Some(Self {
+            base_note    : bytes[ 0 ]      ,
+            detune       : bytes[ 1 ] as i8,
+            gain         : bytes[ 2 ] as i8,
+            low_note     : bytes[ 3 ]      ,
+            high_note    : bytes[ 4 ]      ,
+            low_velocity : bytes[ 5 ]      ,
+            high_velocity: bytes[ 6 ]
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec< u8 > {
+        vec![
+            self.base_note     ,
+            self.detune as u8  ,
+            self.gain   as u8  ,
+            self.low_note      ,
+            self.high_note     ,
+            self.low_velocity  ,
+            self.high_velocity
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ utils::fixture::{ written, read }, common::{ Sample, FileFormat } };
+    use super::{ LoopType, SampleLoop, Sampler, Instrument };
+
+    #[test]
+    fn round_trips() {
+        let mut sampler = Sampler::with_sample_rate(44100);
+        sampler.loops.push(SampleLoop::new(10, 20));
+        sampler.loops.push(SampleLoop { loop_type: LoopType::Other(7), play_count: 3, ..SampleLoop::new(30, 40) });
+        sampler.sampler_data = vec![1, 2, 3];
+
+        let instrument = Instrument { base_note: 48, detune: -12, gain: -3, ..Instrument::default() };
+        let file       = written(FileFormat::new(Sample::I16, 1, 44100), |writer| {
+            writer.add_sampler(&sampler).unwrap();
+            writer.add_instrument(&instrument).unwrap();
+            writer.write(&[0.0; 50]).unwrap();
+        });
+        let mut reader = read(file);
+        assert!(reader.sampler().unwrap() == sampler);
+        assert!(reader.instrument().unwrap() == instrument);
+        assert_eq!(sampler.sample_period, 22675);
+    }
+
+    #[test]
+    fn rejects_malformed_chunks() {
+        assert_eq!(Sampler::with_sample_rate(0).sample_period, 0);
+        assert!(Sampler::from_bytes(&[0; 35]).is_none());
+        assert!(Instrument::from_bytes(&[0; 6]).is_none());
+
+        let mut sampler = Sampler::default();
+        sampler.loops.push(SampleLoop::new(0, 1));
+        let mut bytes = sampler.to_bytes().unwrap();
+        bytes.truncate(bytes.len() - 1);
+        assert!(Sampler::from_bytes(&bytes).is_none());
+
+        sampler.loops.clear();
+        sampler.sampler_data = vec![5; 8];
+        let mut bytes = sampler.to_bytes().unwrap();
+        bytes.truncate(bytes.len() - 4);
+        assert_eq!(Sampler::from_bytes(&bytes).unwrap().sampler_data, [5; 4]);
+    }
+}
@@ -0,0 +1,201 @@
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum FrameRate {
+    Fps24         ,
+    Fps25         ,
+    Fps2997Drop   ,
+    Fps2997NonDrop,
+    Fps30
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Timecode {
+    hours  : u8       ,
+    minutes: u8       ,
+    seconds: u8       ,
+    frames : u8       ,
+    rate   : FrameRate
+}
+
+const DAY_SECONDS: u64 = 24 * 60 * 60;
+
+impl FrameRate {
+    pub fn nominal(&self) -> u64 {
+        match self {
+            FrameRate::Fps24          => 24,
+            FrameRate::Fps25          => 25,
+            FrameRate::Fps2997Drop    => 30,
+            FrameRate::Fps2997NonDrop => 30,
+            FrameRate::Fps30          => 30
+        }
+    }
+
+    pub fn is_drop(&self) -> bool {
+        *self == FrameRate::Fps2997Drop
+    }
+
+    fn ratio(&self) -> (u64, u64) {
+        match self {
+            FrameRate::Fps2997Drop | FrameRate::Fps2997NonDrop => (30000, 1001),
+            _                                                  => (self.nominal(), 1)
+        }
+    }
+
+    fn frames_per_day(&self) -> u64 {
+        match self {
+            FrameRate::Fps2997Drop => DAY_SECONDS * 30 - 2 * (24 * 60 - 24 * 6),
+            _                      => DAY_SECONDS * self.nominal()
+        }
+    }
+}
+
+impl Timecode {
+    pub fn new(hours: u8, minutes: u8, seconds: u8, frames: u8, rate: FrameRate) -> Option< Self > {
+        if hours >= 24 || minutes >= 60 || seconds >= 60 || frames as u64 >= rate.nominal() {
+            return None
+        }
+
+        if rate.is_drop() && seconds == 0 && frames < 2 && !minutes.is_multiple_of(10) {
+            return None
+        }
+
+        Some(Self { hours, minutes, seconds, frames, rate })
+    }
+
+    pub fn from_frame_count(count: u64, rate: FrameRate) -> Self {
+        let count   = count % rate.frames_per_day();
+        let nominal = rate.nominal();
+        let count   = if rate.is_drop() {
+            let tens   = count / 17982;
+            let rest   = count % 17982;
+            let skip   = if rest < 2 { 18 * tens } else { 18 * tens + 2 * ((rest - 2) / 1798) };
+            count + skip
+        }
+        else {
+            count
+        };
+
+        Self {
+            hours  : ( count / (nominal * 3600)      ) as u8,
+            minutes: ((count / (nominal * 60  )) % 60) as u8,
+            seconds: ((count /  nominal        ) % 60) as u8,
+            frames : ( count %  nominal              ) as u8,
+            rate
+        }
+    }
+
+    pub fn to_frame_count(&self) -> u64 {
+        let nominal = self.rate.nominal();
+        let seconds = self.hours as u64 * 3600 + self.minutes as u64 * 60 + self.seconds as u64;
+        let count   = seconds * nominal + self.frames as u64;
+
+        if self.rate.is_drop() {
+            let minutes = self.hours as u64 * 60 + self.minutes as u64;
+            count - 2 * (minutes - minutes / 10)
+        }
+        else {
+            count
+        }
+    }
+
+    pub fn from_samples(samples: u64, sample_rate: u32, rate: FrameRate) -> Option< Self > {
+        if sample_rate == 0 {
+            return None
+        }
+
+        let (num, den) = rate.ratio();
+        let count      = samples as u128 * num as u128 / (sample_rate as u128 * den as u128);
+        Some(Self::from_frame_count((count % rate.frames_per_day() as u128) as u64, rate))
+    }
+
+    pub fn to_samples(&self, sample_rate: u32) -> u64 {
+        let (num, den) = self.rate.ratio();
+        (self.to_frame_count() as u128 * sample_rate as u128 * den as u128).div_ceil(num as u128) as u64
+    }
+
+    pub fn hours(&self) -> u8 {
+        self.hours
+    }
+
+    pub fn minutes(&self) -> u8 {
+        self.minutes
+    }
+
+    pub fn seconds(&self) -> u8 {
+        self.seconds
+    }
+
+    pub fn frames(&self) -> u8 {
+        self.frames
+    }
+
+    pub fn rate(&self) -> FrameRate {
+        self.rate
+    }
+}
+
+impl std::fmt::Display for Timecode {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let separator = if self.rate.is_drop() { ';' } else { ':' };
+        write!(f, "{:02}:{:02}:{:02}{}{:02}", self.hours, self.minutes, self.seconds, separator, self.frames)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ utils::fixture::{ written, read }, common::{ Sample, FileFormat, ChunkPosition }, metadata::Bext };
+    use super::{ FrameRate, Timecode };
+
+    fn format() -> FileFormat {
+        FileFormat::new(Sample::I16, 1, 48000)
+    }
+
+    #[test]
+    fn round_trips_through_bext() {
+        for rate in [FrameRate::Fps24, FrameRate::Fps25, FrameRate::Fps2997Drop, FrameRate::Fps2997NonDrop, FrameRate::Fps30] {
+            let timecode = Timecode::new(1, 2, 3, 4, rate).unwrap();
+            let file     = written(format(), |writer| {
+                writer.set_start_timecode(timecode).unwrap();
+                writer.write(&[0.0]).unwrap();
+            });
+            let mut reader = read(file);
+            let next       = Timecode::new(1, 2, 3, 5, rate).unwrap();
+            let frame      = next.to_samples(48000) - timecode.to_samples(48000);
+            assert!(reader.start_timecode(rate).unwrap() == timecode);
+            assert!(reader.timecode_at(frame as usize, rate).unwrap() == next);
+        }
+
+        let timecode = Timecode::new(10, 0, 0, 0, FrameRate::Fps25).unwrap();
+        let file     = written(format(), |writer| {
+            writer.add_bext(&Bext { description: "kept".to_owned(), ..Bext::default() }).unwrap();
+            writer.set_start_timecode(timecode).unwrap();
+        });
+        let mut reader = read(file);
+        assert_eq!(reader.bext().unwrap().description, "kept");
+        assert_eq!(reader.bext().unwrap().time_reference, 10 * 3600 * 48000);
+    }
+
+    #[test]
+    fn rejects_invalid_timecodes() {
+        assert!(Timecode::new(24, 0, 0, 0, FrameRate::Fps25).is_none());
+        assert!(Timecode::new(0, 0, 0, 25, FrameRate::Fps25).is_none());
+        assert!(Timecode::new(0, 1, 0, 0, FrameRate::Fps2997Drop).is_none());
+        assert!(Timecode::new(0, 10, 0, 0, FrameRate::Fps2997Drop).is_some());
+
+        let timecode = Timecode::new(0, 0, 1, 0, FrameRate::Fps25).unwrap();
+        written(format(), |writer| {
+            writer.add_chunk(b"bext", &[0; 100], ChunkPosition::BeforeData).unwrap();
+            assert!(writer.set_start_timecode(timecode).is_none());
+        });
+
+        assert!(Timecode::from_samples(48000, 0, FrameRate::Fps25).is_none());
+        assert!(Timecode::from_samples(u64::MAX, 1, FrameRate::Fps30).is_some());
+
+        let file = written(FileFormat::new(Sample::I16, 1, 0), |writer| {
+            writer.add_bext(&Bext { time_reference: u64::MAX, ..Bext::default() }).unwrap();
+            writer.write(&[0.0]).unwrap();
+        });
+        let mut reader = read(file);
+        assert!(reader.start_timecode(FrameRate::Fps25).is_none());
+        assert!(reader.timecode_at(1, FrameRate::Fps25).is_none());
+    }
+}
@@ -1,21 +1,40 @@
 use std::io::{ Read, Seek };
-use crate::{ utils::Loader, common::{ AudioFormat, Sample, FileFormat } };
+use crate::{ utils::Loader, metadata::{ Bext, FrameRate, Timecode, Info, Id3Tag, Cues, Sampler, Instrument, Acid, Ixml, PeakEnvelope, Cart, Peak }, common::{ AudioFormat, Sample, FileFormat, ChunkPosition, Chunk, ReadOptions, Issue, Diagnostic, RIFF_HEADER_SIZE, decode }, chunk_reader::ChunkReader };
 
+#[allow(clippy::upper_case_acronyms)]
 enum RiffType {
     RIFF,
     RF64(Option< (u64, u64) >)
 }
 
+enum Span {
+    Data   (usize, usize),
+    Silence(usize       )
+}
+
+#[derive(Clone, Copy)]
+struct Segment {
+    offset: Option< usize >,
+    len   : usize
+}
+
 pub struct Reader< F: Read + Seek > {
-    loader: Loader< F >,
-    format: FileFormat ,
-    begin : usize      ,
-    end   : usize
+    loader  : Loader< F >      ,
+    format  : FileFormat       ,
+    chunks  : Vec< Chunk >     ,
+    warnings: Vec< Diagnostic >,
+    segments: Vec< Segment >   ,
+    pos     : usize
 }
 
 impl< F: Read + Seek > Reader< F > {
     pub fn from(from: F) -> Option< Reader< F > > {
-        let mut loader = Loader::from(from);
+        Self::from_with(from, ReadOptions::new())
+    }
+
+    pub fn from_with(from: F, options: ReadOptions) -> Option< Reader< F > > {
+        let mut loader   = Loader::from(from);
+        let mut warnings = Vec::< Diagnostic >::new();
 
         let riff_id       : [u8; 4] = loader.cload()?;
         let riff_file_size: u32     = loader.cload()?;
@@ -26,16 +45,36 @@ impl< F: Read + Seek > Reader< F > {
         }
 
         let mut riff = match &riff_id {
-            b"RIFF" if riff_file_size as usize == loader.len() => RiffType::RIFF      ,
-            b"RF64" if riff_file_size          == 0xFFFFFFFF   => RiffType::RF64(None),
-            _                                                  => return None
+            b"RIFF"                                   => RiffType::RIFF      ,
+            b"RF64" if riff_file_size == 0xFFFFFFFF   => RiffType::RF64(None),
+            _                                         => return None
         };
 
+        if let RiffType::RIFF = riff {
+            let issue = Issue::RiffSizeMismatch { stored: riff_file_size as usize, actual: loader.len() - 8 };
+            Self::check(&mut warnings, options, riff_file_size as usize + 8 == loader.len(), Diagnostic::new(4, issue))?;
+        }
+
         let mut format = Option::< FileFormat     >::None;
-        let mut be     = Option::< (usize, usize) >::None;
+        let mut spans  = Option::< Vec< Span >    >::None;
+        let mut fact   = Option::< (usize, u32)   >::None;
+        let mut chunks = Vec   ::< Chunk          >::new();
 
         while loader.end().is_none() {
+            let chunk_pos = loader.pos();
+
+            if chunk_pos + 8 > loader.len() {
+                let issue = Issue::ChunkTruncated { id: *b"    " };
+                Self::check(&mut warnings, options, false, Diagnostic::new(chunk_pos, issue))?;
+                break
+            }
+
             let chunk_id: [u8; 4] = loader.cload()?;
+            let position          = match (&chunk_id, &spans) {
+                (b"data", _      ) => ChunkPosition::Data      ,
+                (_      , None   ) => ChunkPosition::BeforeData,
+                (_      , Some(_)) => ChunkPosition::AfterData
+            };
 
             match &chunk_id {
                 b"ds64" => {
@@ -43,23 +82,23 @@ impl< F: Read + Seek > Reader< F > {
                         return None
                     }
 
-                    let ds64_chunk_size  : u64 = loader.cload()?;
+                    let ds64_chunk_size  : u32 = loader.cload()?;
                     let ds64_end_pos           = loader.pos  () + ds64_chunk_size as usize;
                     let ds64_file_size   : u64 = loader.cload()?;
                     let ds64_data_size   : u64 = loader.cload()?;
                     let ds64_sample_count: u64 = loader.cload()?;
 
-                    if ds64_file_size as usize != loader.len() {
-                        return None
-                    }
+                    let issue = Issue::Ds64SizeMismatch { stored: ds64_file_size as usize, actual: loader.len() - 8 };
+                    Self::check(&mut warnings, options, ds64_file_size == loader.len() as u64 - 8, Diagnostic::new(chunk_pos + 8, issue))?;
 
                     riff = RiffType::RF64(Some((ds64_data_size, ds64_sample_count)));
+                    chunks.push(Chunk::new(chunk_id, chunk_pos, ds64_chunk_size as usize, position));
 
                     if loader.pos() > ds64_end_pos {
                         return None
                     }
 
-                    loader.seek(ds64_end_pos)?;
+                    Self::skip_pad(&mut loader, &mut warnings, chunk_id, ds64_end_pos, ds64_chunk_size as usize, options)?;
                 },
                 b"fmt " => {
                     let fmt_chunk_size  : u32 = loader.cload()?;
@@ -71,75 +110,172 @@ impl< F: Read + Seek > Reader< F > {
                     let fmt_block_align : u16 = loader.cload()?;
                     let fmt_bit_depth   : u16 = loader.cload()?;
 
-                    if fmt_byte_rate != fmt_sample_rate * fmt_num_channels as u32 * fmt_bit_depth as u32 / 8 {
+                    if fmt_num_channels == 0 {
                         return None
                     }
 
-                    if fmt_block_align != fmt_num_channels * fmt_bit_depth as u16 / 8 {
-                        return None
-                    }
+                    let byte_rate = fmt_sample_rate as usize * fmt_num_channels as usize * fmt_bit_depth as usize / 8;
+                    let issue     = Issue::ByteRateMismatch { stored: fmt_byte_rate as usize, actual: byte_rate };
+                    Self::check(&mut warnings, options, fmt_byte_rate as usize == byte_rate, Diagnostic::new(chunk_pos + 16, issue))?;
+
+                    let block_align = fmt_num_channels as usize * fmt_bit_depth as usize / 8;
+                    let issue       = Issue::BlockAlignMismatch { stored: fmt_block_align as usize, actual: block_align };
+                    Self::check(&mut warnings, options, fmt_block_align as usize == block_align, Diagnostic::new(chunk_pos + 20, issue))?;
 
                     let audio_format = AudioFormat::new(fmt_audio_format)?;
                     let sample       = Sample::new(audio_format, fmt_bit_depth)?;
                     format           = Some(FileFormat::new(sample, fmt_num_channels, fmt_sample_rate));
+                    chunks.push(Chunk::new(chunk_id, chunk_pos, fmt_chunk_size as usize, position));
 
                     if loader.pos() > fmt_end_pos {
                         return None
                     }
 
-                    loader.seek(fmt_end_pos)?;
+                    Self::skip_pad(&mut loader, &mut warnings, chunk_id, fmt_end_pos, fmt_chunk_size as usize, options)?;
                 },
                 b"data" => {
-                    let data_chunk_size: u32 = loader.cload  ()?;
+                    let data_size_field: u32 = loader.cload  ()?;
                     let data_begin_pos       = loader.pos() ;
                     let data_chunk_size      = match riff {
-                        RiffType::RF64(Some((ds64_data_size, ..))) if data_chunk_size == 0xFFFFFFFF => ds64_data_size  as usize,
-                        RiffType::RIFF                                                              => data_chunk_size as usize,
-                        _                                                                           => return None
+                        RiffType::RF64(Some((u64::MAX      , ..)))                                  => None,
+                        RiffType::RF64(Some((ds64_data_size, ..))) if data_size_field == 0xFFFFFFFF => Some(ds64_data_size  as usize),
+                        RiffType::RIFF if data_size_field == 0xFFFFFFFF                             => None,
+                        RiffType::RIFF if data_size_field == 0 && options.recover()                 => {
+                            if data_begin_pos == loader.len() || loader.chunk_at(data_begin_pos) {
+                                Some(0)
+                            }
+                            else {
+                                None
+                            }
+                        },
+                        RiffType::RIFF                                                              => Some(data_size_field as usize),
+                        _                                                                           => None
+                    };
+                    let data_chunk_size = match data_chunk_size {
+                        Some(data_chunk_size) => data_chunk_size,
+                        None                  => {
+                            let issue = Issue::DataSizeUnset { stored: data_size_field as usize };
+                            Self::check(&mut warnings, options, false, Diagnostic::new(chunk_pos + 4, issue))?;
+
+                            match (fact, format) {
+                                (Some((_, frames)), Some(format)) if frames != 0xFFFFFFFF => {
+                                    (frames as usize * format.block_align() as usize).min(loader.len() - data_begin_pos)
+                                },
+                                _ => loader.len() - data_begin_pos
+                            }
+                        }
+                    };
+                    let data_chunk_size = if data_chunk_size > loader.len() - data_begin_pos {
+                        let issue = Issue::DataTruncated { stored: data_chunk_size, actual: loader.len() - data_begin_pos };
+                        Self::check(&mut warnings, options, false, Diagnostic::new(chunk_pos + 4, issue))?;
+                        loader.len() - data_begin_pos
+                    }
+                    else {
+                        data_chunk_size
                     };
                     let data_end_pos = data_begin_pos + data_chunk_size;
-                    be               = Some((data_begin_pos, data_begin_pos + data_chunk_size));
-                    loader.seek(data_end_pos)?;
+                    spans            = Some(vec![Span::Data(data_begin_pos, data_end_pos)]);
+                    chunks.push(Chunk::new(chunk_id, chunk_pos, data_chunk_size, position));
+                    Self::skip_pad(&mut loader, &mut warnings, chunk_id, data_end_pos, data_chunk_size, options)?;
                 },
                 _ => {
                     let chunk_size: u32 = loader.cload()?;
                     let end_offset      = loader.pos() + chunk_size as usize;
-                    loader.seek(end_offset)?;
+
+                    if end_offset > loader.len() {
+                        let issue = Issue::ChunkTruncated { id: chunk_id };
+                        Self::check(&mut warnings, options, false, Diagnostic::new(chunk_pos, issue))?;
+                        break
+                    }
+
+                    if &chunk_id == b"fact" && chunk_size >= 4 {
+                        fact = Some((chunk_pos + 8, loader.cload()?));
+                    }
+
+                    if &chunk_id == b"LIST" && chunk_size >= 4 && &loader.cload::< [u8; 4] >()? == b"wavl" {
+                        spans = Some(Self::load_wavl(&mut loader, end_offset)?);
+                        chunks.push(Chunk::new(chunk_id, chunk_pos, chunk_size as usize, ChunkPosition::Data));
+                    }
+                    else {
+                        chunks.push(Chunk::new(chunk_id, chunk_pos, chunk_size as usize, position));
+                    }
+
+                    Self::skip_pad(&mut loader, &mut warnings, chunk_id, end_offset, chunk_size as usize, options)?;
                 },
             }
         }
 
         let format       = format?;
-        let (begin, end) = be    ?;
-        let blen         = end - begin;
-        let bs           = format.sample().depth() as usize * format.num_channels() as usize;
+        let spans        = spans ?;
+        let depth        = format.sample().depth() as usize;
+        let bs           = depth * format.num_channels() as usize;
+        let mut segments = Vec::with_capacity(spans.len());
+
+        for span in spans {
+            match span {
+                Span::Data(begin, end) => {
+                    let blen = end - begin;
+
+                    if blen % bs != 0 {
+                        let issue = Issue::PartialFrame { extra: blen % bs };
+                        Self::check(&mut warnings, options, false, Diagnostic::new(begin, issue))?;
+                    }
 
-        if blen % bs != 0 {
-            return None
+                    segments.push(Segment { offset: Some(begin), len: (blen - blen % bs) / depth });
+                },
+                Span::Silence(frames) => {
+                    segments.push(Segment { offset: None, len: frames * format.num_channels() as usize });
+                }
+            }
         }
 
-        if let RiffType::RF64(Some((_, sample_count))) = riff && sample_count as usize != blen / bs {
-            return None
+        let frames = segments.iter().map(|segment| segment.len).sum::< usize >() / format.num_channels() as usize;
+
+        if let RiffType::RF64(Some((_, sample_count))) = riff {
+            let issue = Issue::SampleCountMismatch { stored: sample_count as usize, actual: frames };
+            Self::check(&mut warnings, options, sample_count as usize == frames, Diagnostic::new(RIFF_HEADER_SIZE + 24, issue))?;
         }
 
-        Some(Reader { loader, format, begin, end })
-    }
+        if let Some((offset, stored)) = fact {
+            if stored != 0xFFFFFFFF && stored as usize != frames {
+                warnings.push(Diagnostic::new(offset, Issue::FactMismatch { stored: stored as usize, actual: frames }));
+            }
+        }
 
-    pub fn len(&self) -> usize {
-        (self.end - self.begin) / self.format.sample().depth() as usize
+        Some(Reader { loader, format, chunks, warnings, segments, pos: 0 })
     }
 
-    pub fn format(&self) -> FileFormat {
-        self.format
-    }
+    fn load_wavl(loader: &mut Loader< F >, end: usize) -> Option< Vec< Span > > {
+        let mut spans = Vec::new();
 
-    pub fn pos(&mut self) -> usize {
-        (self.loader.pos() - self.begin) / self.format.sample().depth() as usize
+        while loader.pos() + 8 <= end {
+            let id  : [u8; 4] = loader.cload()?;
+            let size: u32     = loader.cload()?;
+            let begin         = loader.pos();
+            let stop          = begin + size as usize;
+
+            if stop > end {
+                return None
+            }
+
+            match &id {
+                b"data"              => spans.push(Span::Data(begin, stop)),
+                b"slnt" if size >= 4 => spans.push(Span::Silence(loader.cload::< u32 >()? as usize)),
+                _                    => {}
+            }
+
+            loader.seek(stop + size as usize % 2)?;
+        }
+
+        Some(spans)
     }
 
-    pub fn skip(&mut self, n: usize) -> Option< () > {
-        if self.loader.pos() + n * self.format.sample().depth() as usize <= self.end {
-            self.loader.skip(n * self.format.sample().depth() as usize)?;
+    fn check(warnings: &mut Vec< Diagnostic >, options: ReadOptions, ok: bool, diagnostic: Diagnostic) -> Option< () > {
+        if ok {
+            Some(())
+        }
+        else if options.recover() {
+            warnings.push(diagnostic);
             Some(())
         }
         else {
@@ -147,19 +283,169 @@ impl< F: Read + Seek > Reader< F > {
         }
     }
 
-    pub fn rewind(&mut self, n: usize) -> Option< () > {
-        if self.loader.pos() >= self.begin + n * self.format.sample().depth() as usize {
-            self.loader.rewind(n * self.format.sample().depth() as usize)?;
-            Some(())
+    fn skip_pad(loader: &mut Loader< F >, warnings: &mut Vec< Diagnostic >, id: [u8; 4], end: usize, size: usize, options: ReadOptions) -> Option< () > {
+        if size.is_multiple_of(2) {
+            return loader.seek(end)
+        }
+
+        if end == loader.len() || (options.tolerate_missing_pad() && loader.chunk_at(end)) {
+            warnings.push(Diagnostic::new(end, Issue::MissingPad { id }));
+            loader.seek(end)
         }
         else {
-            None
+            loader.seek(end + 1)
         }
     }
 
+    pub fn len(&self) -> usize {
+        self.segments.iter().map(|segment| segment.len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn format(&self) -> FileFormat {
+        self.format
+    }
+
+    pub fn warnings(&self) -> &[Diagnostic] {
+        &self.warnings
+    }
+
+    pub fn chunks(&self) -> &[Chunk] {
+        &self.chunks
+    }
+
+    pub fn find_chunk(&self, id: &[u8; 4]) -> Option< Chunk > {
+        self.chunks.iter().find(|chunk| &chunk.id() == id).copied()
+    }
+
+    pub fn chunk_reader(&mut self, chunk: &Chunk) -> ChunkReader< '_, F > {
+        ChunkReader::new(&mut self.loader, chunk)
+    }
+
+    #[cfg(feature = "async")]
+    pub(crate) fn get_mut(&mut self) -> &mut F {
+        self.loader.get_mut()
+    }
+
+    pub fn read_chunk(&mut self, chunk: &Chunk) -> Option< Vec< u8 > > {
+        let mut ret = vec![0u8; chunk.size()];
+        let restore = self.loader.pos();
+        self.loader.seek(chunk.data_offset())?;
+        let result  = self.loader.load(ret.as_mut_slice());
+        self.loader.seek(restore)?;
+        result.map(|_| ret)
+    }
+
+    pub fn bext(&mut self) -> Option< Bext > {
+        let chunk = self.find_chunk(b"bext")?;
+        Bext::from_bytes(&self.read_chunk(&chunk)?)
+    }
+
+    fn read_list(&mut self, list_type: &[u8; 4]) -> Option< Vec< u8 > > {
+        let chunks = self.chunks.iter().filter(|chunk| &chunk.id() == b"LIST").copied().collect::< Vec< _ > >();
+
+        for chunk in chunks {
+            let data = self.read_chunk(&chunk)?;
+
+            if data.starts_with(list_type) {
+                return Some(data)
+            }
+        }
+
+        None
+    }
+
+    pub fn info(&mut self) -> Option< Info > {
+        Info::from_bytes(&self.read_list(b"INFO")?)
+    }
+
+    pub fn id3(&mut self) -> Option< Id3Tag > {
+        let chunk = self.find_chunk(b"id3 ").or_else(|| self.find_chunk(b"ID3 "))?;
+        Id3Tag::from_bytes(&self.read_chunk(&chunk)?)
+    }
+
+    pub fn cues(&mut self) -> Option< Cues > {
+        let chunk = self.find_chunk(b"cue ")?;
+        let cue   = self.read_chunk(&chunk)?;
+        let adtl  = self.read_list(b"adtl");
+        Cues::from_bytes(&cue, adtl.as_deref())
+    }
+
+    pub fn sampler(&mut self) -> Option< Sampler > {
+        let chunk = self.find_chunk(b"smpl")?;
+        Sampler::from_bytes(&self.read_chunk(&chunk)?)
+    }
+
+    pub fn instrument(&mut self) -> Option< Instrument > {
+        let chunk = self.find_chunk(b"inst")?;
+        Instrument::from_bytes(&self.read_chunk(&chunk)?)
+    }
+
+    pub fn acid(&mut self) -> Option< Acid > {
+        let chunk = self.find_chunk(b"acid")?;
+        Acid::from_bytes(&self.read_chunk(&chunk)?)
+    }
+
+    pub fn ixml_raw(&mut self) -> Option< String > {
+        let chunk = self.find_chunk(b"iXML")?;
+        let data  = self.read_chunk(&chunk)?;
+        let len   = data.iter().position(|&c| c == 0).unwrap_or(data.len());
+        Some(String::from_utf8_lossy(&data[ ..len ]).into_owned())
+    }
+
+    pub fn ixml(&mut self) -> Option< Ixml > {
+        Ixml::from_xml(&self.ixml_raw()?)
+    }
+
+    pub fn peak_envelope(&mut self) -> Option< PeakEnvelope > {
+        let chunk = self.find_chunk(b"levl")?;
+        PeakEnvelope::from_bytes(&self.read_chunk(&chunk)?)
+    }
+
+    pub fn xmp(&mut self) -> Option< String > {
+        let chunk = self.find_chunk(b"_PMX")?;
+        let data  = self.read_chunk(&chunk)?;
+        let len   = data.iter().position(|&c| c == 0).unwrap_or(data.len());
+        Some(String::from_utf8_lossy(&data[ ..len ]).into_owned())
+    }
+
+    pub fn peak(&mut self) -> Option< Peak > {
+        let chunk = self.find_chunk(b"PEAK")?;
+        Peak::from_bytes(&self.read_chunk(&chunk)?)
+    }
+
+    pub fn cart(&mut self) -> Option< Cart > {
+        let chunk = self.find_chunk(b"cart")?;
+        Cart::from_bytes(&self.read_chunk(&chunk)?)
+    }
+
+    pub fn start_timecode(&mut self, rate: FrameRate) -> Option< Timecode > {
+        self.timecode_at(0, rate)
+    }
+
+    pub fn timecode_at(&mut self, frame: usize, rate: FrameRate) -> Option< Timecode > {
+        let bext = self.bext()?;
+        Timecode::from_samples(bext.time_reference.checked_add(frame as u64)?, self.format.sample_rate(), rate)
+    }
+
+    pub fn pos(&mut self) -> usize {
+        self.pos
+    }
+
+    pub fn skip(&mut self, n: usize) -> Option< () > {
+        self.seek(self.pos + n)
+    }
+
+    pub fn rewind(&mut self, n: usize) -> Option< () > {
+        self.seek(self.pos.checked_sub(n)?)
+    }
+
     pub fn seek(&mut self, n: usize) -> Option< () > {
-        if self.begin + n * self.format.sample().depth() as usize <= self.end {
-            self.loader.seek(self.begin + n * self.format.sample().depth() as usize)?;
+        if n <= self.len() {
+            self.pos = n;
             Some(())
         }
         else {
@@ -167,53 +453,241 @@ impl< F: Read + Seek > Reader< F > {
         }
     }
 
-    pub fn read(&mut self, to: &mut [f32]) -> Option< () > {
-        match self.format.sample() {
-            Sample::U8 => {
-                const A: f32 = 2.0 / u8::MAX as f32;
+    pub(crate) fn runs(&self, n: usize) -> Option< Vec< (Option< usize >, usize) > > {
+        if self.pos + n > self.len() {
+            return None
+        }
 
-                for x in to {
-                    let value: u8 = self.loader.cload()?;
-                    *x = (value as f32 * A) - 1.0;
-                }
-            },
-            Sample::I16 => {
-                const A: f32 = 1.0 / i16::MAX as f32;
+        let depth     = self.format.sample().depth() as usize;
+        let mut runs  = Vec::new();
+        let mut start = 0;
+        let mut done  = 0;
 
-                for x in to {
-                    let value: i16 = self.loader.cload()?;
-                    *x = value as f32 / A;
-                }
-            }
-            Sample::I24 => {
-                const A: f32 = 1.0 / 0x7FFFFF as f32;
-
-                for x in to {
-                    let bytes: [u8; 3] = self.loader.cload()?;
-                    let third          = (bytes[ 2 ] >> 7) * 0xFF;
-                    let value          = i32::from_le_bytes([bytes[ 0 ], bytes[ 1 ], bytes[ 2 ], third]);
-                    *x = value as f32 * A;
-                }
-            }
-            Sample::I32 => {
-                const A: f32 = 1.0 / i32::MAX as f32;
+        for segment in &self.segments {
+            let end = start + segment.len;
+            let pos = self.pos + done;
 
-                for x in to {
-                    let value: i32 = self.loader.cload()?;
-                    *x = value as f32 * A;
-                }
+            if done < n && pos < end {
+                let m = (end - pos).min(n - done);
+                runs.push((segment.offset.map(|offset| offset + (pos - start) * depth), m));
+                done += m;
             }
-            Sample::F32 => {
-                self.loader.load(to)?;
-            }
-            Sample::F64 => {
-                for x in to {
-                    let value: f64 = self.loader.cload()?;
-                    *x = value as f32;
+
+            start = end;
+        }
+
+        Some(runs)
+    }
+
+    pub(crate) fn load_at(&mut self, offset: usize, to: &mut [u8]) -> Option< () > {
+        self.loader.seek(offset)?;
+        self.loader.load(to)
+    }
+
+    pub fn read(&mut self, to: &mut [f32]) -> Option< () > {
+        let sample    = self.format.sample();
+        let depth     = sample.depth() as usize;
+        let mut bytes = Vec::new();
+        let mut done  = 0;
+
+        for (offset, n) in self.runs(to.len())? {
+            let to = &mut to[ done..done + n ];
+
+            match offset {
+                Some(offset) => {
+                    bytes.resize(n * depth, 0);
+                    self.load_at(offset, &mut bytes)?;
+                    decode(sample, &bytes, to);
+                },
+                None => {
+                    bytes.clear();
+                    bytes.resize(n * depth, sample.silence());
+                    decode(sample, &bytes, to);
                 }
             }
+
+            done += n;
         }
 
+        self.pos += done;
         Some(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::{ Cursor, Read };
+    use crate::{ utils::fixture::{ chunk, riff, fmt_pcm16, pcm16, written, read, format }, common::{ ChunkPosition, ReadOptions, Issue } };
+    use super::Reader;
+
+    #[test]
+    fn lists_and_reads_chunks() {
+        let file = riff(&[
+            fmt_pcm16(1, 8000),
+            chunk(b"abcd", b"lead"),
+            chunk(b"data", &pcm16(&[1, 2])),
+            chunk(b"wxyz", b"tail")
+        ]);
+        let mut reader = Reader::from(Cursor::new(file)).unwrap();
+
+        let ids = reader.chunks().iter().map(|chunk| (chunk.id(), chunk.position())).collect::< Vec< _ > >();
+        assert!(ids == [(*b"fmt ", ChunkPosition::BeforeData), (*b"abcd", ChunkPosition::BeforeData), (*b"data", ChunkPosition::Data), (*b"wxyz", ChunkPosition::AfterData)]);
+
+        let wxyz = reader.find_chunk(b"wxyz").unwrap();
+        assert_eq!(wxyz.size(), 4);
+        assert_eq!(reader.read_chunk(&wxyz).unwrap(), b"tail");
+
+        let abcd     = reader.find_chunk(b"abcd").unwrap();
+        let mut data = Vec::new();
+        reader.chunk_reader(&abcd).read_to_end(&mut data).unwrap();
+        assert_eq!(data, b"lead");
+        assert!(reader.find_chunk(b"none").is_none());
+        assert_eq!(reader.len(), 2);
+    }
+
+    #[test]
+    fn rejects_chunk_past_end() {
+        let mut file = riff(&[fmt_pcm16(1, 8000), chunk(b"data", &pcm16(&[1, 2])), chunk(b"iXML", b"<x/>")]);
+        let len      = file.len();
+        file.truncate(len - 2);
+        file[ 4..8 ].copy_from_slice(&(len as u32 - 10).to_le_bytes());
+
+        assert!(Reader::from(Cursor::new(file.clone())).is_none());
+
+        let reader = Reader::from_with(Cursor::new(file), ReadOptions::new().with_recover(true)).unwrap();
+        assert!(reader.find_chunk(b"iXML").is_none());
+        assert!(reader.warnings().iter().any(|warning| matches!(warning.issue(), Issue::ChunkTruncated { id } if &id == b"iXML")));
+    }
+
+    #[test]
+    fn skips_pad_bytes() {
+        let file = written(format(), |writer| {
+            writer.add_chunk(b"abcd", b"odd", ChunkPosition::BeforeData).unwrap();
+            writer.write(&[0.5]).unwrap();
+            writer.add_chunk(b"wxyz", b"pad", ChunkPosition::AfterData).unwrap();
+        });
+        assert_eq!(file.len() % 2, 0);
+
+        let reader = read(file);
+        let ids    = reader.chunks().iter().map(|chunk| chunk.id()).collect::< Vec< _ > >();
+        assert_eq!(ids, [*b"fmt ", *b"abcd", *b"data", *b"wxyz"]);
+        assert!(reader.warnings().is_empty());
+    }
+
+    #[test]
+    fn handles_missing_pad() {
+        let mut unpadded = b"iXML".to_vec();
+        unpadded.extend_from_slice(&3u32.to_le_bytes());
+        unpadded.extend_from_slice(b"<a>");
+
+        let middle = riff(&[fmt_pcm16(1, 8000), unpadded.clone(), chunk(b"data", &pcm16(&[1]))]);
+        assert!(Reader::from(Cursor::new(middle.clone())).is_none());
+
+        let reader = Reader::from_with(Cursor::new(middle), ReadOptions::new().with_tolerate_missing_pad(true)).unwrap();
+        assert!(reader.warnings().iter().any(|warning| matches!(warning.issue(), Issue::MissingPad { id } if &id == b"iXML")));
+        assert_eq!(reader.len(), 1);
+
+        let last   = riff(&[fmt_pcm16(1, 8000), chunk(b"data", &pcm16(&[1])), unpadded]);
+        let reader = Reader::from(Cursor::new(last)).unwrap();
+        assert_eq!(reader.find_chunk(b"iXML").unwrap().size(), 3);
+    }
+
+    #[test]
+    fn recovers_clean_file_unchanged() {
+        let file       = written(format(), |writer| writer.write(&[0.5, -0.5, 0.25]).unwrap());
+        let mut strict = read(file.clone());
+        let mut loose  = Reader::from_with(Cursor::new(file), ReadOptions::new().with_recover(true)).unwrap();
+        assert!(loose.warnings().is_empty());
+
+        let mut a = vec![0f32; strict.len()];
+        let mut b = vec![0f32; loose .len()];
+        strict.read(&mut a).unwrap();
+        loose .read(&mut b).unwrap();
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn recovers_damaged_sizes() {
+        let recover = ReadOptions::new().with_recover(true);
+
+        let mut unset = riff(&[fmt_pcm16(1, 8000), chunk(b"data", &pcm16(&[1, 2, 3]))]);
+        unset[ 4..8 ].copy_from_slice(&0u32.to_le_bytes());
+        unset[ 40..44 ].copy_from_slice(&0u32.to_le_bytes());
+        assert!(Reader::from(Cursor::new(unset.clone())).is_none());
+
+        let reader = Reader::from_with(Cursor::new(unset), recover).unwrap();
+        assert_eq!(reader.len(), 3);
+        assert!(reader.warnings().iter().any(|warning| matches!(warning.issue(), Issue::DataSizeUnset { .. })));
+
+        let mut truncated = riff(&[fmt_pcm16(1, 8000), chunk(b"data", &pcm16(&[1, 2, 3, 4]))]);
+        truncated.truncate(truncated.len() - 3);
+        assert!(Reader::from(Cursor::new(truncated.clone())).is_none());
+
+        let reader = Reader::from_with(Cursor::new(truncated), recover).unwrap();
+        assert_eq!(reader.len(), 2);
+        assert!(reader.warnings().iter().any(|warning| matches!(warning.issue(), Issue::DataTruncated { stored: 8, actual: 5 })));
+    }
+
+    #[test]
+    fn rejects_impossible_sizes() {
+        let recover = ReadOptions::new().with_recover(true);
+
+        let mut silent = fmt_pcm16(1, 8000);
+        silent[ 10..12 ].copy_from_slice(&0u16.to_le_bytes());
+        assert!(Reader::from_with(Cursor::new(riff(&[silent, chunk(b"data", &pcm16(&[1]))])), recover).is_none());
+
+        let data     = pcm16(&[1, 2]);
+        let mut ds64 = Vec::new();
+        ds64.extend_from_slice(&0u64.to_le_bytes());
+        ds64.extend_from_slice(&(u64::MAX - 1).to_le_bytes());
+        ds64.extend_from_slice(&2u64.to_le_bytes());
+        ds64.extend_from_slice(&0u32.to_le_bytes());
+
+        let mut file = riff(&[chunk(b"ds64", &ds64), fmt_pcm16(1, 8000), chunk(b"data", &data)]);
+        let len      = file.len();
+        file[ ..8 ].copy_from_slice(b"RF64\xFF\xFF\xFF\xFF");
+        file[ 20..28 ].copy_from_slice(&(len as u64).to_le_bytes());
+        file[ len - data.len() - 4..len - data.len() ].copy_from_slice(&0xFFFFFFFFu32.to_le_bytes());
+        assert!(Reader::from(Cursor::new(file.clone())).is_none());
+
+        let reader = Reader::from_with(Cursor::new(file.clone()), recover).unwrap();
+        assert_eq!(reader.len(), 2);
+        assert!(reader.warnings().iter().any(|warning| matches!(warning.issue(), Issue::DataTruncated { actual: 4, .. })));
+
+        file[ 28..36 ].copy_from_slice(&u64::MAX.to_le_bytes());
+        let reader = Reader::from_with(Cursor::new(file), recover).unwrap();
+        assert_eq!(reader.len(), 2);
+        assert!(reader.warnings().iter().any(|warning| matches!(warning.issue(), Issue::DataSizeUnset { .. })));
+    }
+
+    #[test]
+    fn reads_wave_lists() {
+        let mut wavl = b"wavl".to_vec();
+        wavl.extend(chunk(b"data", &pcm16(&[0x4000])));
+        wavl.extend(chunk(b"slnt", &2u32.to_le_bytes()));
+        wavl.extend(chunk(b"data", &pcm16(&[-0x7FFF, 0x2000])));
+
+        let mut reader = read(riff(&[fmt_pcm16(1, 8000), chunk(b"LIST", &wavl), chunk(b"iXML", b"<x/>")]));
+        let list       = reader.find_chunk(b"LIST").unwrap();
+        assert!(list.position() == ChunkPosition::Data);
+        assert!(reader.find_chunk(b"iXML").unwrap().position() == ChunkPosition::AfterData);
+        assert_eq!(reader.len(), 5);
+
+        let mut samples = [1f32; 5];
+        reader.seek(1).unwrap();
+        reader.read(&mut samples[ 1..4 ]).unwrap();
+        assert_eq!(samples, [1.0, 0.0, 0.0, -1.0, 1.0]);
+        assert!(reader.read(&mut samples[ ..2 ]).is_none());
+    }
+
+    #[test]
+    fn rejects_overrunning_wave_list() {
+        let mut wavl = b"wavl".to_vec();
+        wavl.extend(chunk(b"data", &pcm16(&[1, 2])));
+        let len      = wavl.len();
+        wavl[ 8..12 ].copy_from_slice(&(len as u32).to_le_bytes());
+
+        assert!(Reader::from(Cursor::new(riff(&[fmt_pcm16(1, 8000), chunk(b"LIST", &wavl)]))).is_none());
+        assert!(Reader::from(Cursor::new(riff(&[fmt_pcm16(1, 8000), chunk(b"LIST", b"wavl")]))).unwrap().is_empty());
+    }
+}
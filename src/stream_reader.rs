@@ -0,0 +1,211 @@
+use std::io::Read;
+use crate::{ utils::StreamLoader, common::{ AudioFormat, Sample, FileFormat, ChunkPosition, Chunk, Issue, Diagnostic, decode } };
+
+pub struct StreamReader< R: Read > {
+    loader   : StreamLoader< R >,
+    format   : FileFormat       ,
+    chunks   : Vec< Chunk >     ,
+    warnings : Vec< Diagnostic >,
+    remaining: Option< usize >  ,
+    buffer   : Vec< u8 >        ,
+    pos      : usize
+}
+
+impl< R: Read > StreamReader< R > {
+    pub fn from(from: R) -> Option< StreamReader< R > > {
+        let mut loader = StreamLoader::from(from);
+
+        let riff_id       : [u8; 4] = loader.cload()?;
+        let _riff_size    : u32     = loader.cload()?;
+        let riff_format_id: [u8; 4] = loader.cload()?;
+
+        if !matches!(&riff_id, b"RIFF" | b"RF64") || &riff_format_id != b"WAVE" {
+            return None
+        }
+
+        let mut format    = Option::< FileFormat >::None;
+        let mut ds64_data = Option::< u64        >::None;
+        let mut chunks    = Vec   ::< Chunk      >::new();
+
+        loop {
+            let chunk_pos           = loader.pos  () ;
+            let chunk_id  : [u8; 4] = loader.cload()?;
+            let chunk_size: u32     = loader.cload()?;
+
+            match &chunk_id {
+                b"ds64" if &riff_id == b"RF64" && chunk_size >= 24 => {
+                    let _file_size   : u64 = loader.cload()?;
+                    let data_size    : u64 = loader.cload()?;
+                    let _sample_count: u64 = loader.cload()?;
+                    ds64_data              = Some(data_size);
+                    loader.skip(chunk_size as usize - 24 + chunk_size as usize % 2)?;
+                },
+                b"fmt " if chunk_size >= 16 => {
+                    let audio_format: u16 = loader.cload()?;
+                    let num_channels: u16 = loader.cload()?;
+                    let sample_rate : u32 = loader.cload()?;
+                    let _byte_rate  : u32 = loader.cload()?;
+                    let _block_align: u16 = loader.cload()?;
+                    let bit_depth   : u16 = loader.cload()?;
+
+                    if num_channels == 0 {
+                        return None
+                    }
+
+                    let sample = Sample::new(AudioFormat::new(audio_format)?, bit_depth)?;
+                    format     = Some(FileFormat::new(sample, num_channels, sample_rate));
+                    loader.skip(chunk_size as usize - 16 + chunk_size as usize % 2)?;
+                },
+                b"data" => {
+                    // Only the all-ones markers mean the writer never got to patch the size; zero is an empty data chunk
+                    let remaining = match (ds64_data, chunk_size) {
+                        (Some(data_size), 0xFFFFFFFF) if data_size != u64::MAX => usize::try_from(data_size).ok(),
+                        (_              , 0xFFFFFFFF)                          => None,
+                        (_              , size      )                          => Some(size as usize)
+                    };
+
+                    chunks.push(Chunk::new(chunk_id, chunk_pos, remaining.unwrap_or(0), ChunkPosition::Data));
+                    return Some(StreamReader { loader, format: format?, chunks, warnings: Vec::new(), remaining, buffer: Vec::new(), pos: 0 })
+                },
+                _ => {
+                    loader.skip(chunk_size as usize + chunk_size as usize % 2)?;
+                }
+            }
+
+            chunks.push(Chunk::new(chunk_id, chunk_pos, chunk_size as usize, ChunkPosition::BeforeData));
+        }
+    }
+
+    pub fn format(&self) -> FileFormat {
+        self.format
+    }
+
+    pub fn chunks(&self) -> &[Chunk] {
+        &self.chunks
+    }
+
+    pub fn warnings(&self) -> &[Diagnostic] {
+        &self.warnings
+    }
+
+    pub fn len(&self) -> Option< usize > {
+        self.remaining.map(|remaining| self.pos + remaining / self.format.sample().depth() as usize)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == Some(0)
+    }
+
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    pub fn read(&mut self, to: &mut [f32]) -> Option< usize > {
+        if self.remaining == Some(0) {
+            return Some(0)
+        }
+
+        let sample   = self.format.sample();
+        let depth    = sample.depth() as usize;
+        let channels = self.format.num_channels() as usize;
+        let want   = match self.remaining {
+            Some(remaining) => (to.len() * depth).min(remaining - remaining % depth),
+            None            =>  to.len() * depth
+        };
+
+        self.buffer.resize(want, 0);
+        let got = self.loader.fill(&mut self.buffer)?;
+        let n   = got / depth;
+
+        decode(sample, &self.buffer[ ..n * depth ], &mut to[ ..n ]);
+        self.pos += n;
+
+        // Bytes left after the last whole sample; the data ends once fewer than a sample remain
+        let rest = match self.remaining {
+            Some(remaining) if got == want => remaining - got,
+            None            if got == want => return Some(n),
+            _                              => got % depth
+        };
+
+        if rest >= depth {
+            self.remaining = Some(rest);
+            return Some(n)
+        }
+
+        self.remaining = Some(0);
+        let extra      = self.pos % channels * depth + rest;
+
+        if extra != 0 {
+            let offset = self.loader.pos() - got % depth - self.pos % channels * depth;
+            self.warnings.push(Diagnostic::new(offset, Issue::PartialFrame { extra }));
+        }
+
+        Some(n)
+    }
+
+    pub fn into_inner(self) -> R {
+        self.loader.into_inner()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ utils::fixture::{ chunk, riff, fmt_pcm16, pcm16, written, read }, common::{ Sample, FileFormat, ChunkPosition, Issue } };
+    use super::StreamReader;
+
+    #[test]
+    fn matches_reader() {
+        let file = written(FileFormat::new(Sample::I24, 2, 8000), |writer| {
+            writer.add_chunk(b"abcd", b"odd", ChunkPosition::BeforeData).unwrap();
+            writer.write(&[0.5, -0.5, 0.25, -0.25, 0.125, 0.0]).unwrap();
+        });
+        let mut reader = read(file.clone());
+        let mut stream = StreamReader::from(file.as_slice()).unwrap();
+        let ids        = stream.chunks().iter().map(|chunk| chunk.id()).collect::< Vec< _ > >();
+        assert_eq!(ids, [*b"fmt ", *b"abcd", *b"data"]);
+        assert_eq!(stream.len(), Some(6));
+
+        let mut a = [0f32; 6];
+        let mut b = [0f32; 8];
+        reader.read(&mut a).unwrap();
+        assert_eq!(stream.read(&mut b[ ..4 ]), Some(4));
+        assert_eq!(stream.read(&mut b[ 4.. ]), Some(2));
+        assert_eq!(stream.read(&mut b[ 6.. ]), Some(0));
+        assert_eq!(b[ ..6 ], a);
+        assert_eq!(stream.pos(), 6);
+    }
+
+    #[test]
+    fn handles_unknown_and_malformed_input() {
+        let mut file = riff(&[fmt_pcm16(1, 8000), chunk(b"data", &pcm16(&[1, 2, 3]))]);
+        file[ 40..44 ].copy_from_slice(&0xFFFFFFFFu32.to_le_bytes());
+        file.push(0);
+
+        let mut stream = StreamReader::from(file.as_slice()).unwrap();
+        let mut to     = [0f32; 8];
+        assert_eq!(stream.len(), None);
+        assert_eq!(stream.read(&mut to), Some(3));
+        assert_eq!(stream.read(&mut to), Some(0));
+        assert!(stream.warnings().iter().map(|warning| warning.issue()).eq([Issue::PartialFrame { extra: 1 }]));
+
+        assert!(StreamReader::from(&b"RIFF\x04\x00\x00\x00AVI "[ .. ]).is_none());
+        assert!(StreamReader::from(riff(&[chunk(b"data", &pcm16(&[1]))]).as_slice()).is_none());
+        assert!(StreamReader::from(&riff(&[fmt_pcm16(1, 8000)])[ ..30 ]).is_none());
+    }
+    #[test]
+    fn reads_empty_and_partial_data() {
+        let mut to     = [0f32; 4];
+        let file       = riff(&[fmt_pcm16(1, 8000), chunk(b"data", &[]), chunk(b"abcd", &pcm16(&[1, 2]))]);
+        let mut stream = StreamReader::from(file.as_slice()).unwrap();
+        assert_eq!(stream.len(), Some(0));
+        assert_eq!(stream.read(&mut to), Some(0));
+
+        let file       = riff(&[fmt_pcm16(2, 8000), chunk(b"data", &[pcm16(&[1, 2, 3]), vec![4]].concat())]);
+        let mut stream = StreamReader::from(file.as_slice()).unwrap();
+        assert_eq!(stream.read(&mut to), Some(3));
+        assert_eq!(stream.read(&mut to), Some(0));
+        assert_eq!(stream.warnings().len(), 1);
+        assert_eq!(stream.warnings()[ 0 ].offset(), 48);
+        assert!(stream.warnings()[ 0 ].issue() == Issue::PartialFrame { extra: 3 });
+    }
+}
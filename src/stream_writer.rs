@@ -0,0 +1,158 @@
+use std::io::Write;
+use crate::{ utils::StreamSaver, common::{ FileFormat, encode, header } };
+
+const SILENCE_SAMPLES: usize = 4096;
+
+pub struct StreamWriter< W: Write > {
+    saver    : StreamSaver< W >,
+    format   : FileFormat      ,
+    len      : Option< usize > ,
+    written  : usize           ,
+    buffer   : Vec< u8 >       ,
+    finalized: bool
+}
+
+impl< W: Write > StreamWriter< W > {
+    pub fn to(to: W, format: FileFormat, frames: Option< usize >) -> Option< StreamWriter< W > > {
+        Self::start(to, format, frames, false)
+    }
+
+    pub fn to_rf64(to: W, format: FileFormat, frames: Option< usize >) -> Option< StreamWriter< W > > {
+        Self::start(to, format, frames, true)
+    }
+
+    fn start(to: W, format: FileFormat, frames: Option< usize >, rf64: bool) -> Option< StreamWriter< W > > {
+        let mut saver = StreamSaver::to(to);
+        saver.save(header(format, rf64, frames)?.as_slice())?;
+
+        let len = frames.map(|frames| frames * format.num_channels() as usize);
+        Some(StreamWriter { saver, format, len, written: 0, buffer: Vec::new(), finalized: false })
+    }
+
+    pub fn format(&self) -> FileFormat {
+        self.format
+    }
+
+    pub fn len(&self) -> Option< usize > {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.written == 0
+    }
+
+    pub fn pos(&self) -> usize {
+        self.written
+    }
+
+    pub fn write(&mut self, from: &[f32]) -> Option< () > {
+        if self.finalized || self.len.is_some_and(|len| self.written + from.len() > len) {
+            return None
+        }
+
+        self.buffer.clear();
+        encode(self.format.sample(), from, &mut self.buffer);
+        self.saver.save(self.buffer.as_slice())?;
+        self.written += from.len();
+        Some(())
+    }
+
+    pub fn finalize(&mut self) -> Option< () > {
+        if self.finalized {
+            return Some(())
+        }
+
+        if let Some(len) = self.len {
+            let silence = [0f32; SILENCE_SAMPLES];
+
+            while self.written < len {
+                let n = (len - self.written).min(SILENCE_SAMPLES);
+                self.write(&silence[ ..n ])?;
+            }
+
+            if (len * self.format.sample().depth() as usize) % 2 == 1 {
+                self.saver.save(&0u8)?;
+            }
+        }
+
+        self.finalized = true;
+        self.saver.flush()
+    }
+}
+
+impl< W: Write > Drop for StreamWriter< W > {
+    // Errors cannot surface from here; call finalize() first to see them.
+    fn drop(&mut self) {
+        let _ = self.finalize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use crate::{ utils::fixture::{ read, format }, common::{ Sample, FileFormat, ReadOptions, header }, reader::Reader, writer::Writer };
+    use super::StreamWriter;
+
+    const SAMPLES: [f32; 6] = [0.5, -0.5, 0.25, -0.25, 0.125, 0.0];
+
+    #[test]
+    fn matches_writer() {
+        for rf64 in [false, true] {
+            for sample in [Sample::U8, Sample::I24, Sample::F32] {
+                let format     = FileFormat::new(sample, 1, 8000);
+                let mut file   = Cursor::new(Vec::new());
+                let mut writer = if rf64 { Writer::to_rf64(&mut file, format) } else { Writer::to(&mut file, format) }.unwrap();
+                writer.write(&SAMPLES[ ..5 ]).unwrap();
+                writer.finalize().unwrap();
+                drop(writer);
+
+                let mut out    = Vec::new();
+                let mut stream = if rf64 { StreamWriter::to_rf64(&mut out, format, Some(5)) } else { StreamWriter::to(&mut out, format, Some(5)) }.unwrap();
+                stream.write(&SAMPLES[ ..2 ]).unwrap();
+                stream.write(&SAMPLES[ 2..5 ]).unwrap();
+                stream.finalize().unwrap();
+                drop(stream);
+
+                assert_eq!(out, file.into_inner());
+            }
+        }
+    }
+
+    #[test]
+    fn pads_and_streams_unknown_lengths() {
+        let format     = FileFormat::new(Sample::I16, 2, 8000);
+        let mut out    = Vec::new();
+        let mut stream = StreamWriter::to(&mut out, format, Some(3)).unwrap();
+        stream.write(&SAMPLES[ ..2 ]).unwrap();
+        drop(stream);
+
+        let mut reader  = read(out);
+        let mut samples = [1f32; 6];
+        reader.read(&mut samples).unwrap();
+        assert_eq!(&samples[ 2.. ], &[0.0; 4]);
+
+        let mut out    = Vec::new();
+        let mut stream = StreamWriter::to_rf64(&mut out, format, None).unwrap();
+        assert_eq!(stream.len(), None);
+        stream.write(&SAMPLES).unwrap();
+        stream.finalize().unwrap();
+        drop(stream);
+
+        let reader = Reader::from_with(Cursor::new(out), ReadOptions::new().with_recover(true)).unwrap();
+        assert_eq!(reader.len(), 6);
+    }
+
+    #[test]
+    fn rejects_overlong_and_late_writes() {
+        let format     = format();
+        let mut stream = StreamWriter::to(Vec::new(), format, Some(2)).unwrap();
+        assert!(stream.write(&SAMPLES[ ..3 ]).is_none());
+        stream.write(&SAMPLES[ ..2 ]).unwrap();
+        stream.finalize().unwrap();
+        assert!(stream.write(&SAMPLES[ ..1 ]).is_none());
+
+        assert!(StreamWriter::to(Vec::new(), format, Some(usize::MAX)).is_none());
+        assert!(StreamWriter::to(Vec::new(), format, Some(1 << 31)).is_none());
+        assert!(header(format, true, Some(1 << 31)).is_some());
+    }
+}
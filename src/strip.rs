@@ -0,0 +1,231 @@
+use std::io::{ Read, Write, Seek };
+use crate::{ utils::Saver, common::{ ChunkPosition, Chunk, RIFF_HEADER_SIZE, DS64_CHUNK_SIZE }, reader::Reader };
+
+const COPY_BLOCK_SIZE: usize = 1 << 16;
+
+pub fn strip_metadata< F: Read + Seek, T: Write + Seek >(from: F, to: T, keep: &[[u8; 4]]) -> Option< () > {
+    let mut reader = Reader::from(from)?;
+    let rf64       = reader.find_chunk(b"ds64").is_some();
+    let chunks     = reader.chunks().iter().filter(|chunk| match &chunk.id() {
+        _ if chunk.position() == ChunkPosition::Data => true,
+        b"fmt " | b"fact"                            => true,
+        b"ds64" | b"RIFF" | b"RF64"                  => false,
+        id                                           => keep.contains(id)
+    }).copied().collect::< Vec< Chunk > >();
+    let mut saver  = Saver::to(to);
+    let mut data   = None;
+
+    saver.save(if rf64 { b"RF64" } else { b"RIFF" })?;
+    saver.save(&0xFFFFFFFFu32)?;
+    saver.save(b"WAVE")?;
+
+    if rf64 {
+        saver.save(b"ds64")?;
+        saver.save(&(DS64_CHUNK_SIZE as u32 - 8))?;
+        saver.save(&[0u64; 3])?;
+        saver.save(&0u32)?; // Table length
+    }
+
+    for chunk in chunks {
+        if chunk.position() != ChunkPosition::Data {
+            saver.save_chunk(&chunk.id(), &reader.read_chunk(&chunk)?)?;
+            continue
+        }
+
+        if &chunk.id() != b"data" {
+            let size = reader.len() * reader.format().sample().depth() as usize;
+
+            saver.save(b"data")?;
+            saver.save(&if rf64 { 0xFFFFFFFF } else { u32::try_from(size).ok()? })?;
+            flatten(&mut reader, &mut saver)?;
+
+            if size % 2 == 1 {
+                saver.save(&0u8)?;
+            }
+
+            data = Some(size);
+            continue
+        }
+
+        saver.save(b"data")?;
+        saver.save(&if rf64 { 0xFFFFFFFF } else { u32::try_from(chunk.size()).ok()? })?;
+
+        let mut from   = reader.chunk_reader(&chunk);
+        let mut buffer = vec![0u8; COPY_BLOCK_SIZE];
+
+        loop {
+            let n = from.read(&mut buffer).ok()?;
+
+            if n == 0 {
+                break
+            }
+
+            saver.save(&buffer[ ..n ])?;
+        }
+
+        if from.remaining() != 0 {
+            return None
+        }
+
+        if chunk.size() % 2 == 1 {
+            saver.save(&0u8)?;
+        }
+
+        data = Some(chunk.size());
+    }
+
+    let data_size = data?;
+    let file_size = saver.pos();
+
+    if rf64 {
+        saver.seek(RIFF_HEADER_SIZE + 8)?;
+        saver.save(&(file_size as u64 - 8))?;
+        saver.save(&(data_size as u64))?;
+        saver.save(&((data_size / reader.format().block_align() as usize) as u64))?;
+    }
+    else {
+        saver.seek(4)?;
+        saver.save(&u32::try_from(file_size - 8).ok()?)?;
+    }
+
+    saver.seek(file_size)
+}
+
+fn flatten< F: Read + Seek, T: Write + Seek >(reader: &mut Reader< F >, saver: &mut Saver< T >) -> Option< () > {
+    let depth      = reader.format().sample().depth() as usize;
+    let silence    = reader.format().sample().silence();
+    let mut buffer = vec![0u8; COPY_BLOCK_SIZE];
+    reader.seek(0)?;
+
+    for (mut offset, n) in reader.runs(reader.len())? {
+        let mut remaining = n * depth;
+
+        while remaining > 0 {
+            let m = remaining.min(COPY_BLOCK_SIZE);
+
+            match &mut offset {
+                Some(offset) => {
+                    reader.load_at(*offset, &mut buffer[ ..m ])?;
+                    *offset += m;
+                },
+                None => buffer[ ..m ].fill(silence)
+            }
+
+            saver.save(&buffer[ ..m ])?;
+            remaining -= m;
+        }
+    }
+
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use crate::{ utils::fixture::{ chunk, riff, fmt_pcm16, pcm16 }, common::{ Sample, FileFormat }, metadata::Bext, reader::Reader, writer::Writer };
+    use super::strip_metadata;
+
+    fn samples(file: Vec< u8 >) -> Vec< f32 > {
+        let mut reader = Reader::from(Cursor::new(file)).unwrap();
+        let mut ret    = vec![0f32; reader.len()];
+        reader.read(&mut ret).unwrap();
+        ret
+    }
+
+    #[test]
+    fn keeps_audio_and_allowed_chunks() {
+        let file = riff(&[
+            fmt_pcm16(1, 8000),
+            chunk(b"JUNK", &[0; 10]),
+            chunk(b"iXML", b"<BWFXML/>"),
+            chunk(b"data", &pcm16(&[1, -2, 3])),
+            chunk(b"id3 ", b"ID3")
+        ]);
+        let mut out = Cursor::new(Vec::new());
+        strip_metadata(Cursor::new(file.clone()), &mut out, &[*b"iXML"]).unwrap();
+
+        let out    = out.into_inner();
+        let reader = Reader::from(Cursor::new(out.clone())).unwrap();
+        let ids    = reader.chunks().iter().map(|chunk| chunk.id()).collect::< Vec< _ > >();
+        assert_eq!(ids, [*b"fmt ", *b"iXML", *b"data"]);
+        assert_eq!(samples(out), samples(file));
+    }
+
+    #[test]
+    fn flattens_wave_list() {
+        let mut wavl = b"wavl".to_vec();
+        wavl.extend(chunk(b"data", &pcm16(&[0x4000, 0x2000])));
+        wavl.extend(chunk(b"slnt", &2u32.to_le_bytes()));
+        wavl.extend(chunk(b"data", &pcm16(&[-0x4000])));
+
+        let file    = riff(&[fmt_pcm16(1, 8000), chunk(b"LIST", &wavl)]);
+        let mut out = Cursor::new(Vec::new());
+        strip_metadata(Cursor::new(file.clone()), &mut out, &[]).unwrap();
+
+        let out    = out.into_inner();
+        let reader = Reader::from(Cursor::new(out.clone())).unwrap();
+        assert!(reader.find_chunk(b"LIST").is_none());
+        assert_eq!(reader.find_chunk(b"data").unwrap().size(), 10);
+        assert_eq!(samples(out), samples(file));
+    }
+
+    #[test]
+    fn flattens_u8_wave_list() {
+        let mut fmt = Vec::new();
+        fmt.extend_from_slice(&1u16.to_le_bytes());
+        fmt.extend_from_slice(&1u16.to_le_bytes());
+        fmt.extend_from_slice(&8000u32.to_le_bytes());
+        fmt.extend_from_slice(&8000u32.to_le_bytes());
+        fmt.extend_from_slice(&1u16.to_le_bytes());
+        fmt.extend_from_slice(&8u16.to_le_bytes());
+
+        let mut wavl = b"wavl".to_vec();
+        wavl.extend(chunk(b"data", &[0xC0, 0x40, 0xFF]));
+        wavl.extend(chunk(b"slnt", &2u32.to_le_bytes()));
+        wavl.extend(chunk(b"data", &[0x00]));
+
+        let file    = riff(&[chunk(b"fmt ", &fmt), chunk(b"LIST", &wavl)]);
+        let mut out = Cursor::new(Vec::new());
+        strip_metadata(Cursor::new(file.clone()), &mut out, &[]).unwrap();
+
+        let out        = out.into_inner();
+        let mut reader = Reader::from(Cursor::new(out.clone())).unwrap();
+        let data       = reader.find_chunk(b"data").unwrap();
+        assert_eq!(reader.read_chunk(&data).unwrap(), [0xC0, 0x40, 0xFF, 0x80, 0x80, 0x00]);
+        assert_eq!(samples(out), samples(file));
+    }
+
+    #[test]
+    fn rejects_file_without_audio() {
+        let file = riff(&[fmt_pcm16(1, 8000)]);
+        assert!(strip_metadata(Cursor::new(file), Cursor::new(Vec::new()), &[]).is_none());
+    }
+
+    #[test]
+    fn keeps_rf64_layout() {
+        let mut file   = Cursor::new(Vec::new());
+        let mut writer = Writer::to_rf64(&mut file, FileFormat::new(Sample::F32, 2, 8000)).unwrap();
+        writer.add_bext(&Bext::default()).unwrap();
+        writer.write(&[0.5, -0.5, 0.25, -0.25]).unwrap();
+        writer.finalize().unwrap();
+        drop(writer);
+
+        let file    = file.into_inner();
+        let mut out = Cursor::new(Vec::new());
+        strip_metadata(Cursor::new(file.clone()), &mut out, &[]).unwrap();
+
+        let out    = out.into_inner();
+        let reader = Reader::from(Cursor::new(out.clone())).unwrap();
+        let ids    = reader.chunks().iter().map(|chunk| chunk.id()).collect::< Vec< _ > >();
+        assert_eq!(&out[ ..4 ], b"RF64");
+        assert_eq!(ids, [*b"ds64", *b"fmt ", *b"fact", *b"data"]);
+        assert!(reader.warnings().is_empty());
+        assert_eq!(samples(out), samples(file));
+    }
+
+    #[test]
+    fn rejects_non_wave_input() {
+        assert!(strip_metadata(Cursor::new(b"RIFF\x04\x00\x00\x00AVI ".to_vec()), Cursor::new(Vec::new()), &[]).is_none());
+        assert!(strip_metadata(Cursor::new(Vec::new()), Cursor::new(Vec::new()), &[]).is_none());
+    }
+}
@@ -1,4 +1,4 @@
-use core::{ mem::size_of, slice::from_raw_parts_mut };
+use core::{ mem::{ size_of, size_of_val }, slice::from_raw_parts_mut };
 use super::Pod;
 
 pub trait AsU8Slice {
@@ -23,13 +23,13 @@ impl< T: Pod > AsU8Slice for T {
 impl< T: Pod > AsU8Slice for [T] {
     fn as_u8_slice(&self) -> &[u8] {
         let ptr = self.as_ptr() as *mut u8;
-        let len = size_of::< T >() * self.len();
+        let len = size_of_val(self);
         unsafe { from_raw_parts_mut(ptr, len) }
     }
 
     fn as_mut_u8_slice(&mut self) -> &mut [u8] {
         let ptr = self.as_ptr() as *mut u8;
-        let len = size_of::< T >() * self.len();
+        let len = size_of_val(self);
         unsafe { from_raw_parts_mut(ptr, len) }
     }
 }
@@ -0,0 +1,49 @@
+use std::{ io::{ ErrorKind, SeekFrom }, future::poll_fn, pin::Pin };
+use futures_io::{ AsyncRead, AsyncSeek };
+use super::AsU8Slice;
+
+pub struct AsyncLoader< F: AsyncRead + AsyncSeek + Unpin > {
+    from: F
+}
+
+impl< F: AsyncRead + AsyncSeek + Unpin > AsyncLoader< F > {
+    pub fn from(from: F) -> Self {
+        Self {
+            from
+        }
+    }
+
+    pub async fn load< T: AsU8Slice + ?Sized >(&mut self, to: &mut T) -> Option< () > {
+        let mut s = to.as_mut_u8_slice();
+
+        while !s.is_empty() {
+            match poll_fn(|cx| Pin::new(&mut self.from).poll_read(cx, s)).await {
+                Ok(0)                                       => return None,
+                Ok(n)                                       => s = &mut s[ n.. ],
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(_)                                      => return None
+            }
+        }
+
+        Some(())
+    }
+
+    async fn seek_from(&mut self, pos: SeekFrom) -> Option< usize > {
+        poll_fn(|cx| Pin::new(&mut self.from).poll_seek(cx, pos)).await.ok().map(|n| n as usize)
+    }
+
+    pub async fn seek(&mut self, n: usize) -> Option< () > {
+        self.seek_from(SeekFrom::Start(n as u64)).await.map(|_| ())
+    }
+
+    pub async fn len(&mut self) -> Option< usize > {
+        let pos = self.seek_from(SeekFrom::Current(0)).await?;
+        let len = self.seek_from(SeekFrom::End    (0)).await?;
+        self.seek(pos).await?;
+        Some(len)
+    }
+
+    pub fn into_inner(self) -> F {
+        self.from
+    }
+}
@@ -0,0 +1,42 @@
+use std::{ io::{ ErrorKind, SeekFrom }, future::poll_fn, pin::Pin };
+use futures_io::{ AsyncWrite, AsyncSeek };
+use super::AsU8Slice;
+
+pub struct AsyncSaver< T: AsyncWrite + AsyncSeek + Unpin > {
+    to: T
+}
+
+impl< T: AsyncWrite + AsyncSeek + Unpin > AsyncSaver< T > {
+    pub fn to(to: T) -> Self {
+        Self {
+            to
+        }
+    }
+
+    pub async fn save< F: AsU8Slice + ?Sized >(&mut self, from: &F) -> Option< () > {
+        let mut s = from.as_u8_slice();
+
+        while !s.is_empty() {
+            match poll_fn(|cx| Pin::new(&mut self.to).poll_write(cx, s)).await {
+                Ok(0)                                       => return None,
+                Ok(n)                                       => s = &s[ n.. ],
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(_)                                      => return None
+            }
+        }
+
+        Some(())
+    }
+
+    pub async fn seek(&mut self, n: usize) -> Option< () > {
+        poll_fn(|cx| Pin::new(&mut self.to).poll_seek(cx, SeekFrom::Start(n as u64))).await.ok().map(|_| ())
+    }
+
+    pub async fn flush(&mut self) -> Option< () > {
+        poll_fn(|cx| Pin::new(&mut self.to).poll_flush(cx)).await.ok()
+    }
+
+    pub fn into_inner(self) -> T {
+        self.to
+    }
+}
@@ -0,0 +1,106 @@
+use std::io::Cursor;
+use crate::{ common::{ Sample, FileFormat }, reader::Reader, writer::Writer };
+
+pub fn chunk(id: &[u8; 4], data: &[u8]) -> Vec< u8 > {
+    let mut ret = id.to_vec();
+    ret.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    ret.extend_from_slice(data);
+
+    if data.len() % 2 == 1 {
+        ret.push(0);
+    }
+
+    ret
+}
+
+pub fn riff(chunks: &[Vec< u8 >]) -> Vec< u8 > {
+    let body = chunks.concat();
+    let mut ret = b"RIFF".to_vec();
+    ret.extend_from_slice(&(body.len() as u32 + 4).to_le_bytes());
+    ret.extend_from_slice(b"WAVE");
+    ret.extend(body);
+    ret
+}
+
+pub fn fmt_pcm16(num_channels: u16, sample_rate: u32) -> Vec< u8 > {
+    let block_align = num_channels * 2;
+    let mut data    = Vec::new();
+    data.extend_from_slice(&1u16.to_le_bytes());
+    data.extend_from_slice(&num_channels.to_le_bytes());
+    data.extend_from_slice(&sample_rate.to_le_bytes());
+    data.extend_from_slice(&(sample_rate * block_align as u32).to_le_bytes());
+    data.extend_from_slice(&block_align.to_le_bytes());
+    data.extend_from_slice(&16u16.to_le_bytes());
+    chunk(b"fmt ", &data)
+}
+
+pub fn pcm16(samples: &[i16]) -> Vec< u8 > {
+    samples.iter().flat_map(|x| x.to_le_bytes()).collect()
+}
+
+pub fn format() -> FileFormat {
+    FileFormat::new(Sample::I16, 1, 8000)
+}
+
+pub fn written(format: FileFormat, build: impl FnOnce(&mut Writer< &mut Cursor< Vec< u8 > > >)) -> Vec< u8 > {
+    let mut file   = Cursor::new(Vec::new());
+    let mut writer = Writer::to(&mut file, format).unwrap();
+    build(&mut writer);
+    writer.finalize().unwrap();
+    drop(writer);
+    file.into_inner()
+}
+
+pub fn read(file: Vec< u8 >) -> Reader< Cursor< Vec< u8 > > > {
+    Reader::from(Cursor::new(file)).unwrap()
+}
+
+#[cfg(feature = "async")]
+pub use self::future::{ Memory, block_on };
+
+#[cfg(feature = "async")]
+mod future {
+    use std::{ io::{ Cursor, Read, Write, Seek, SeekFrom }, future::Future, pin::{ Pin, pin }, task::{ Context, Poll, Waker } };
+    use futures_io::{ AsyncRead, AsyncWrite, AsyncSeek };
+
+    // An in-memory file that hands out at most three bytes per poll, to exercise partial reads.
+    pub struct Memory(pub Cursor< Vec< u8 > >);
+
+    impl AsyncRead for Memory {
+        fn poll_read(mut self: Pin< &mut Self >, _: &mut Context, buf: &mut [u8]) -> Poll< std::io::Result< usize > > {
+            let n = buf.len().min(3);
+            Poll::Ready(self.0.read(&mut buf[ ..n ]))
+        }
+    }
+
+    impl AsyncWrite for Memory {
+        fn poll_write(mut self: Pin< &mut Self >, _: &mut Context, buf: &[u8]) -> Poll< std::io::Result< usize > > {
+            Poll::Ready(self.0.write(buf))
+        }
+
+        fn poll_flush(self: Pin< &mut Self >, _: &mut Context) -> Poll< std::io::Result< () > > {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_close(self: Pin< &mut Self >, _: &mut Context) -> Poll< std::io::Result< () > > {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    impl AsyncSeek for Memory {
+        fn poll_seek(mut self: Pin< &mut Self >, _: &mut Context, pos: SeekFrom) -> Poll< std::io::Result< u64 > > {
+            Poll::Ready(self.0.seek(pos))
+        }
+    }
+
+    pub fn block_on< T >(future: impl Future< Output = T >) -> T {
+        let mut future = pin!(future);
+        let mut cx     = Context::from_waker(Waker::noop());
+
+        loop {
+            if let Poll::Ready(ret) = future.as_mut().poll(&mut cx) {
+                return ret
+            }
+        }
+    }
+}
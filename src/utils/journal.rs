@@ -0,0 +1,58 @@
+use std::{ io::{ Write, Seek, SeekFrom, Error, ErrorKind }, mem::take };
+
+pub struct Journal {
+    writes: Vec< (usize, Vec< u8 >) >,
+    len   : usize                    ,
+    pos   : usize
+}
+
+impl Journal {
+    pub fn new() -> Self {
+        Self {
+            writes: Vec::new(),
+            len   : 0         ,
+            pos   : 0
+        }
+    }
+
+    pub fn take(&mut self) -> Vec< (usize, Vec< u8 >) > {
+        take(&mut self.writes)
+    }
+
+    fn push(&mut self, offset: usize, data: &[u8]) {
+        match self.writes.last_mut() {
+            Some((begin, last)) if *begin + last.len() == offset => last.extend_from_slice(data),
+            _                                                    => self.writes.push((offset, data.to_vec()))
+        }
+    }
+}
+
+impl Write for Journal {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result< usize > {
+        if self.pos > self.len {
+            self.push(self.len, &vec![0u8; self.pos - self.len]);
+        }
+
+        self.push(self.pos, buf);
+        self.pos += buf.len();
+        self.len  = self.len.max(self.pos);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result< () > {
+        Ok(())
+    }
+}
+
+impl Seek for Journal {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result< u64 > {
+        let pos = match pos {
+            SeekFrom::Start  (n) => Some(n as usize),
+            SeekFrom::End    (n) => self.len.checked_add_signed(n as isize),
+            SeekFrom::Current(n) => self.pos.checked_add_signed(n as isize)
+        };
+
+        self.pos = pos.ok_or(Error::from(ErrorKind::InvalidInput))?;
+        Ok(self.pos as u64)
+    }
+}
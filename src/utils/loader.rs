@@ -1,4 +1,4 @@
-use std::{ io::{ Read, Seek }, mem::MaybeUninit };
+use std::{ io::{ Read, Seek }, mem::zeroed };
 use super::{ AsU8Slice, Pod };
 
 pub struct Loader< F: Read + Seek > {
@@ -17,17 +17,24 @@ impl< F: Read + Seek > Loader< F > {
         self.from.read_exact(s).ok().map(|_| ())
     }
 
+    pub fn read(&mut self, to: &mut [u8]) -> Option< usize > {
+        self.from.read(to).ok()
+    }
+
     pub fn cload< T: Pod >(&mut self) -> Option< T > {
-        let mut ret = unsafe { MaybeUninit::uninit().assume_init() };
+        let mut ret = unsafe { zeroed() };
         self.load(&mut ret).map(|_| ret)
     }
 
-    pub fn skip(&mut self, n: usize) -> Option< () > {
-        self.from.seek(std::io::SeekFrom::Current(n as i64)).ok().map(|_| ())
+    pub fn load_str(&mut self, n: usize) -> Option< String > {
+        let mut bytes = vec![0u8; n];
+        self.load(bytes.as_mut_slice())?;
+        let len = bytes.iter().position(|&c| c == 0).unwrap_or(n);
+        Some(String::from_utf8_lossy(&bytes[ ..len ]).into_owned())
     }
 
-    pub fn rewind(&mut self, n: usize) -> Option< () > {
-        self.from.seek(std::io::SeekFrom::Current(-(n as i64))).ok().map(|_| ())
+    pub fn skip(&mut self, n: usize) -> Option< () > {
+        self.from.seek(std::io::SeekFrom::Current(n as i64)).ok().map(|_| ())
     }
 
     pub fn seek(&mut self, n: usize) -> Option< () > {
@@ -42,6 +49,17 @@ impl< F: Read + Seek > Loader< F > {
         self.from.stream_len().ok().unwrap() as usize
     }
 
+    pub fn chunk_at(&mut self, pos: usize) -> bool {
+        if pos + 8 > self.len() || self.seek(pos).is_none() {
+            return false
+        }
+
+        match self.cload::< [u8; 4] >() {
+            Some(id) => id.iter().all(|c| c.is_ascii_graphic() || *c == b' ') && id[ 0 ] != b' ',
+            None     => false
+        }
+    }
+
     pub fn end(&mut self) -> Option< () > {
         if self.pos() == self.len() {
             Some(())
@@ -50,5 +68,10 @@ impl< F: Read + Seek > Loader< F > {
             None
         }
     }
+
+    #[cfg(feature = "async")]
+    pub fn get_mut(&mut self) -> &mut F {
+        &mut self.from
+    }
 }
 
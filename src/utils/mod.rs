@@ -2,8 +2,30 @@ mod pod;
 mod as_u8_slice;
 mod loader;
 mod saver;
+mod stream_loader;
+mod stream_saver;
+#[cfg(test)]
+pub mod fixture;
+#[cfg(feature = "async")]
+mod async_loader;
+#[cfg(feature = "async")]
+mod async_saver;
+#[cfg(feature = "async")]
+mod sparse;
+#[cfg(feature = "async")]
+mod journal;
 
 pub use pod::Pod;
 pub use as_u8_slice::AsU8Slice;
 pub use loader::Loader;
 pub use saver::Saver;
+pub use stream_loader::StreamLoader;
+pub use stream_saver::StreamSaver;
+#[cfg(feature = "async")]
+pub use async_loader::AsyncLoader;
+#[cfg(feature = "async")]
+pub use async_saver::AsyncSaver;
+#[cfg(feature = "async")]
+pub use sparse::Sparse;
+#[cfg(feature = "async")]
+pub use journal::Journal;
@@ -17,6 +17,34 @@ impl< T: Write + Seek > Saver< T > {
         self.to.write_all(s).ok().map(|_| ())
     }
 
+    pub fn save_str(&mut self, from: &str, n: usize) -> Option< () > {
+        let bytes = from.as_bytes();
+        let len   = bytes.len().min(n);
+        self.save(&bytes[ ..len ])?;
+        self.save(vec![0u8; n - len].as_slice())
+    }
+
+    pub fn save_chunk(&mut self, id: &[u8; 4], data: &[u8]) -> Option< () > {
+        self.save(id)?;
+        self.save(&u32::try_from(data.len()).ok()?)?;
+        self.save(data)?;
+
+        if data.len() % 2 == 1 {
+            self.save(&0u8)?;
+        }
+
+        Some(())
+    }
+
+    pub fn into_inner(self) -> T {
+        self.to
+    }
+
+    #[cfg(feature = "async")]
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.to
+    }
+
     pub fn skip(&mut self, n: usize) -> Option< () > {
         self.to.seek(std::io::SeekFrom::Current(n as i64)).ok().map(|_| ())
     }
@@ -0,0 +1,82 @@
+use std::io::{ Read, Seek, SeekFrom, Error, ErrorKind };
+
+// A file image of which only some ranges are loaded. Reading a range that is not loaded fails and records it as missing.
+pub struct Sparse {
+    parts  : Vec< (usize, Vec< u8 >) >,
+    missing: Option< (usize, usize) > ,
+    len    : usize                    ,
+    pos    : usize
+}
+
+impl Sparse {
+    pub fn new(len: usize) -> Self {
+        Self {
+            parts  : Vec::new(),
+            missing: None      ,
+            len                ,
+            pos    : 0
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn insert(&mut self, offset: usize, data: Vec< u8 >) {
+        self.parts.push((offset, data));
+    }
+
+    pub fn contains(&self, offset: usize, n: usize) -> bool {
+        let end     = (offset + n).min(self.len);
+        let mut pos = offset;
+
+        while pos < end {
+            match self.parts.iter().find(|(begin, data)| *begin <= pos && pos < begin + data.len()) {
+                Some((begin, data)) => pos = begin + data.len(),
+                None                => return false
+            }
+        }
+
+        true
+    }
+
+    pub fn take_missing(&mut self) -> Option< (usize, usize) > {
+        self.missing.take()
+    }
+}
+
+impl Read for Sparse {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result< usize > {
+        let n = buf.len().min(self.len.saturating_sub(self.pos));
+
+        if !self.contains(self.pos, n) {
+            self.missing.get_or_insert((self.pos, n));
+            return Err(Error::from(ErrorKind::WouldBlock))
+        }
+
+        for (offset, data) in &self.parts {
+            let begin = (*offset             ).max(self.pos    );
+            let end   = (*offset + data.len()).min(self.pos + n);
+
+            if begin < end {
+                buf[ begin - self.pos..end - self.pos ].copy_from_slice(&data[ begin - offset..end - offset ]);
+            }
+        }
+
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+impl Seek for Sparse {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result< u64 > {
+        let pos = match pos {
+            SeekFrom::Start  (n) => Some(n as usize),
+            SeekFrom::End    (n) => self.len.checked_add_signed(n as isize),
+            SeekFrom::Current(n) => self.pos.checked_add_signed(n as isize)
+        };
+
+        self.pos = pos.ok_or(Error::from(ErrorKind::InvalidInput))?;
+        Ok(self.pos as u64)
+    }
+}
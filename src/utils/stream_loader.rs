@@ -0,0 +1,64 @@
+use std::{ io::{ Read, ErrorKind, sink, copy }, mem::zeroed };
+use super::{ AsU8Slice, Pod };
+
+pub struct StreamLoader< R: Read > {
+    from: R    ,
+    pos : usize
+}
+
+impl< R: Read > StreamLoader< R > {
+    pub fn from(from: R) -> Self {
+        Self {
+            from  ,
+            pos: 0
+        }
+    }
+
+    pub fn load< T: AsU8Slice + ?Sized >(&mut self, to: &mut T) -> Option< () > {
+        let s = to.as_mut_u8_slice();
+        self.from.read_exact(s).ok()?;
+        self.pos += s.len();
+        Some(())
+    }
+
+    pub fn fill(&mut self, to: &mut [u8]) -> Option< usize > {
+        let mut n = 0;
+
+        while n < to.len() {
+            match self.from.read(&mut to[ n.. ]) {
+                Ok(0)                                       => break,
+                Ok(m)                                       => n += m,
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(_)                                      => return None
+            }
+        }
+
+        self.pos += n;
+        Some(n)
+    }
+
+    pub fn cload< T: Pod >(&mut self) -> Option< T > {
+        let mut ret = unsafe { zeroed() };
+        self.load(&mut ret).map(|_| ret)
+    }
+
+    pub fn skip(&mut self, n: usize) -> Option< () > {
+        let skipped = copy(&mut (&mut self.from).take(n as u64), &mut sink()).ok()? as usize;
+        self.pos += skipped;
+
+        if skipped == n {
+            Some(())
+        }
+        else {
+            None
+        }
+    }
+
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    pub fn into_inner(self) -> R {
+        self.from
+    }
+}
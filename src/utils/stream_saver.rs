@@ -0,0 +1,22 @@
+use std::io::Write;
+use super::AsU8Slice;
+
+pub struct StreamSaver< W: Write > {
+    to: W
+}
+
+impl< W: Write > StreamSaver< W > {
+    pub fn to(to: W) -> Self {
+        Self {
+            to
+        }
+    }
+
+    pub fn save< F: AsU8Slice + ?Sized >(&mut self, from: &F) -> Option< () > {
+        self.to.write_all(from.as_u8_slice()).ok()
+    }
+
+    pub fn flush(&mut self) -> Option< () > {
+        self.to.flush().ok()
+    }
+}
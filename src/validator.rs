@@ -0,0 +1,338 @@
+use std::io::{ Read, Seek };
+use crate::{ utils::Loader, common::{ AudioFormat, Sample, Severity, Issue, Diagnostic } };
+
+pub struct Report {
+    diagnostics: Vec< Diagnostic >
+}
+
+struct Fmt {
+    audio_format: u16  ,
+    block_align : usize
+}
+
+impl Report {
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    pub fn errors(&self) -> impl Iterator< Item = &Diagnostic > {
+        self.diagnostics.iter().filter(|diagnostic| diagnostic.severity() == Severity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator< Item = &Diagnostic > {
+        self.diagnostics.iter().filter(|diagnostic| diagnostic.severity() == Severity::Warning)
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.errors().next().is_none()
+    }
+
+    fn push(&mut self, offset: usize, issue: Issue) {
+        self.diagnostics.push(Diagnostic::new(offset, issue));
+    }
+}
+
+impl std::fmt::Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for diagnostic in &self.diagnostics {
+            writeln!(f, "{}", diagnostic)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub fn validate< F: Read + Seek >(from: F) -> Report {
+    let mut loader = Loader::from(from);
+    let mut report = Report { diagnostics: Vec::new() };
+    walk(&mut loader, &mut report);
+    report
+}
+
+fn walk< F: Read + Seek >(loader: &mut Loader< F >, report: &mut Report) -> Option< () > {
+    let len = loader.len();
+
+    if len < 12 {
+        report.push(0, Issue::NotWave);
+        return None
+    }
+
+    let riff_id       : [u8; 4] = loader.cload()?;
+    let riff_file_size: u32     = loader.cload()?;
+    let riff_format_id: [u8; 4] = loader.cload()?;
+    let rf64                    = &riff_id == b"RF64";
+
+    if !matches!(&riff_id, b"RIFF" | b"RF64") || &riff_format_id != b"WAVE" {
+        report.push(0, Issue::NotWave);
+        return None
+    }
+
+    match rf64 {
+        false if riff_file_size as usize + 8 != len => report.push(4, Issue::RiffSizeMismatch { stored: riff_file_size as usize, actual: len - 8     }),
+        true  if riff_file_size != 0xFFFFFFFF       => report.push(4, Issue::RiffSizeMismatch { stored: riff_file_size as usize, actual: 0xFFFFFFFF }),
+        _                                           => {}
+    }
+
+    let mut ds64  = Option::< (usize, usize) >::None;
+    let mut fmt   = Option::< Fmt            >::None;
+    let mut data  = Option::< usize          >::None;
+    let mut fact  = Option::< (usize, u32)   >::None;
+    let mut wavl  = false;
+    let mut first = true;
+
+    while loader.pos() + 8 <= len {
+        let chunk_pos         = loader.pos  () ;
+        let chunk_id: [u8; 4] = loader.cload()?;
+        let size_field: u32   = loader.cload()?;
+        let payload_pos       = loader.pos  () ;
+
+        let mut size = match (&chunk_id, ds64) {
+            (b"data", Some((data_size, _))) if size_field == 0xFFFFFFFF && data_size != usize::MAX => data_size,
+            (b"data", _                   ) if size_field == 0xFFFFFFFF                            => {
+                report.push(chunk_pos + 4, Issue::DataSizeUnset { stored: size_field as usize });
+                len - payload_pos
+            },
+            _ => size_field as usize
+        };
+
+        if size > len - payload_pos {
+            if &chunk_id != b"data" {
+                report.push(chunk_pos, Issue::ChunkTruncated { id: chunk_id });
+                return None
+            }
+
+            report.push(chunk_pos + 4, Issue::DataTruncated { stored: size, actual: len - payload_pos });
+            size = len - payload_pos;
+        }
+
+        match &chunk_id {
+            b"ds64" => {
+                if !rf64 || !first {
+                    report.push(chunk_pos, Issue::MisplacedChunk { id: chunk_id });
+                }
+
+                if size < 28 {
+                    report.push(chunk_pos, Issue::ChunkTruncated { id: chunk_id });
+                }
+                else {
+                    let file_size   : u64 = loader.cload()?;
+                    let data_size   : u64 = loader.cload()?;
+                    let sample_count: u64 = loader.cload()?;
+
+                    if file_size != len as u64 - 8 {
+                        report.push(payload_pos, Issue::Ds64SizeMismatch { stored: file_size as usize, actual: len - 8 });
+                    }
+
+                    ds64 = Some((data_size as usize, sample_count as usize));
+                }
+            },
+            b"fmt " => {
+                if fmt.is_some() {
+                    report.push(chunk_pos, Issue::DuplicateChunk { id: chunk_id });
+                }
+
+                if size < 16 {
+                    report.push(chunk_pos, Issue::ChunkTruncated { id: chunk_id });
+                }
+                else {
+                    let audio_format: u16 = loader.cload()?;
+                    let num_channels: u16 = loader.cload()?;
+                    let sample_rate : u32 = loader.cload()?;
+                    let byte_rate   : u32 = loader.cload()?;
+                    let block_align : u16 = loader.cload()?;
+                    let bit_depth   : u16 = loader.cload()?;
+
+                    let expected_block_align = num_channels as usize * bit_depth.div_ceil(8) as usize;
+                    let expected_byte_rate   = sample_rate  as usize * expected_block_align;
+
+                    if byte_rate as usize != expected_byte_rate {
+                        report.push(payload_pos + 8, Issue::ByteRateMismatch { stored: byte_rate as usize, actual: expected_byte_rate });
+                    }
+
+                    if block_align as usize != expected_block_align {
+                        report.push(payload_pos + 12, Issue::BlockAlignMismatch { stored: block_align as usize, actual: expected_block_align });
+                    }
+
+                    if AudioFormat::new(audio_format).and_then(|audio_format| Sample::new(audio_format, bit_depth)).is_none() {
+                        report.push(payload_pos, Issue::UnsupportedFormat { audio_format, bit_depth });
+                    }
+
+                    if data.is_none() {
+                        fmt = Some(Fmt { audio_format, block_align: block_align as usize });
+                    }
+                }
+            },
+            b"data" => {
+                if data.is_some() {
+                    report.push(chunk_pos, Issue::DuplicateChunk { id: chunk_id });
+                }
+                else {
+                    match &fmt {
+                        None      => report.push(chunk_pos, Issue::MisplacedChunk { id: chunk_id }),
+                        Some(fmt) => {
+                            if fmt.block_align != 0 && size % fmt.block_align != 0 {
+                                report.push(payload_pos, Issue::PartialFrame { extra: size % fmt.block_align });
+                            }
+
+                            if let Some((_, sample_count)) = ds64 {
+                                if fmt.block_align != 0 && sample_count != size / fmt.block_align {
+                                    report.push(chunk_pos, Issue::SampleCountMismatch { stored: sample_count, actual: size / fmt.block_align });
+                                }
+                            }
+                        }
+                    }
+
+                    match ds64 {
+                        Some((data_size, _)) if size_field != 0xFFFFFFFF && data_size != size_field as usize => {
+                            report.push(chunk_pos, Issue::Ds64DataMismatch { stored: data_size, actual: size_field as usize });
+                        },
+                        None if rf64 => {
+                            report.push(chunk_pos, Issue::MissingChunk { id: *b"ds64" });
+                        },
+                        _ => {}
+                    }
+
+                    if size == 0 {
+                        report.push(chunk_pos, Issue::EmptyData);
+                    }
+
+                    data = Some(size);
+                }
+            },
+            b"fact" => {
+                if size < 4 {
+                    report.push(chunk_pos, Issue::ChunkTruncated { id: chunk_id });
+                }
+                else {
+                    fact = Some((payload_pos, loader.cload()?));
+                }
+            },
+            b"LIST" if size >= 4 && &loader.cload::< [u8; 4] >()? == b"wavl" => {
+                if data.is_some() {
+                    report.push(chunk_pos, Issue::DuplicateChunk { id: *b"data" });
+                }
+
+                data = Some(size);
+                wavl = true;
+            },
+            _ => {}
+        }
+
+        let end = payload_pos + size;
+        first   = false;
+
+        if size % 2 == 1 {
+            if end == len || (!loader.chunk_at(end + 1) && loader.chunk_at(end)) {
+                report.push(end, Issue::MissingPad { id: chunk_id });
+                loader.seek(end)?;
+            }
+            else {
+                loader.seek(end + 1)?;
+            }
+        }
+        else {
+            loader.seek(end)?;
+        }
+    }
+
+    if loader.pos() < len {
+        report.push(loader.pos(), Issue::TrailingBytes { count: len - loader.pos() });
+    }
+
+    if fmt.is_none() {
+        report.push(len, Issue::MissingChunk { id: *b"fmt " });
+    }
+
+    if data.is_none() {
+        report.push(len, Issue::MissingChunk { id: *b"data" });
+    }
+
+    if let (Some(fmt), Some(size)) = (&fmt, data) {
+        match fact {
+            Some((offset, stored)) => {
+                if !wavl && fmt.block_align != 0 && stored != 0xFFFFFFFF && stored as usize != size / fmt.block_align {
+                    report.push(offset, Issue::FactMismatch { stored: stored as usize, actual: size / fmt.block_align });
+                }
+            },
+            None => {
+                if AudioFormat::new(fmt.audio_format).is_none_or(|audio_format| audio_format.requires_fact()) {
+                    report.push(len, Issue::MissingFact);
+                }
+            }
+        }
+    }
+
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use crate::{ utils::fixture::{ chunk, riff, fmt_pcm16, pcm16, written }, common::{ Sample, FileFormat, ChunkPosition, Issue }, writer::Writer };
+    use super::validate;
+
+    fn issues(file: Vec< u8 >) -> Vec< Issue > {
+        validate(Cursor::new(file)).diagnostics().iter().map(|diagnostic| diagnostic.issue()).collect()
+    }
+
+    #[test]
+    fn accepts_writer_output() {
+        let file = written(FileFormat::new(Sample::I24, 1, 48000), |writer| {
+            writer.add_chunk(b"abcd", b"odd", ChunkPosition::BeforeData).unwrap();
+            writer.write(&[0.5, -0.5, 0.25]).unwrap();
+        });
+        assert!(issues(file).is_empty());
+
+        let mut file   = Cursor::new(Vec::new());
+        let mut writer = Writer::to_rf64(&mut file, FileFormat::new(Sample::F32, 2, 48000)).unwrap();
+        writer.write(&[0.5, -0.5]).unwrap();
+        writer.finalize().unwrap();
+        drop(writer);
+        assert!(validate(Cursor::new(file.into_inner())).is_valid());
+    }
+
+    #[test]
+    fn reports_malformed_files() {
+        assert!(issues(b"RIFF".to_vec()) == [Issue::NotWave]);
+
+        let missing = issues(riff(&[chunk(b"data", &pcm16(&[1]))]));
+        assert!(missing.contains(&Issue::MissingChunk { id: *b"fmt " }));
+
+        let mut fmt = fmt_pcm16(1, 8000);
+        fmt[ 16..20 ].copy_from_slice(&1u32.to_le_bytes());
+        let report  = validate(Cursor::new(riff(&[fmt, fmt_pcm16(1, 8000), chunk(b"data", &pcm16(&[1]))])));
+        let issues  = report.diagnostics().iter().map(|diagnostic| diagnostic.issue()).collect::< Vec< _ > >();
+        assert!(!report.is_valid());
+        assert!(issues.contains(&Issue::ByteRateMismatch { stored: 1, actual: 16000 }));
+        assert!(issues.contains(&Issue::DuplicateChunk { id: *b"fmt " }));
+    }
+    #[test]
+    fn reports_impossible_sizes() {
+        let mut short = riff(&[chunk(b"ds64", &[0; 24]), fmt_pcm16(1, 8000), chunk(b"data", &pcm16(&[1]))]);
+        short[ ..8 ].copy_from_slice(b"RF64\xFF\xFF\xFF\xFF");
+        assert!(issues(short).contains(&Issue::ChunkTruncated { id: *b"ds64" }));
+
+        let mut oversized = riff(&[fmt_pcm16(1, 8000), chunk(b"data", &pcm16(&[1])), chunk(b"iXML", b"<x/>")]);
+        let len           = oversized.len();
+        oversized[ len - 8..len - 4 ].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(issues(oversized).contains(&Issue::ChunkTruncated { id: *b"iXML" }));
+
+        let mut ds64 = Vec::new();
+        ds64.extend_from_slice(&u64::MAX.to_le_bytes());
+        ds64.extend_from_slice(&(u64::MAX - 1).to_le_bytes());
+        ds64.extend_from_slice(&1u64.to_le_bytes());
+        ds64.extend_from_slice(&0u32.to_le_bytes());
+
+        let mut huge = riff(&[chunk(b"ds64", &ds64), fmt_pcm16(1, 8000), chunk(b"data", &pcm16(&[1]))]);
+        let len      = huge.len();
+        huge[ ..8 ].copy_from_slice(b"RF64\xFF\xFF\xFF\xFF");
+        huge[ len - 6..len - 2 ].copy_from_slice(&u32::MAX.to_le_bytes());
+
+        let found = issues(huge.clone());
+        assert!(found.contains(&Issue::Ds64SizeMismatch { stored: usize::MAX, actual: len - 8 }));
+        assert!(found.contains(&Issue::DataTruncated { stored: usize::MAX - 1, actual: 2 }));
+
+        huge[ 28..36 ].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(issues(huge).contains(&Issue::DataSizeUnset { stored: 0xFFFFFFFF }));
+    }
+}
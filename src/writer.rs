@@ -1,98 +1,269 @@
-use std::io::{ Write, Seek };
-use crate::{ utils::Saver, common::{ FileFormat, Sample } };
+use std::io::{ Read, Write, Seek };
+use crate::{ utils::Saver, metadata::{ Bext, Timecode, Info, Id3Tag, Cues, Sampler, Instrument, Acid, Ixml, PeakEnvelope, Cart, Peak }, common::{ FileFormat, ChunkPosition, Chunk, encode, header, RIFF_HEADER_SIZE }, reader::Reader };
 
+#[allow(clippy::upper_case_acronyms)]
 enum RiffType {
     RIFF,
     RF64
 }
 
-const RIFF_HEADER_SIZE: usize = 12;
-const DS64_CHUNK_SIZE : usize = 36;
-const FMT_CHUNK_SIZE  : usize = 24;
+const TRANSCODE_FRAMES: usize = 4096;
 
 pub struct Writer< T: Write + Seek > {
-    saver  : Saver< T >,
-    rt     : RiffType  ,
-    format : FileFormat,
-    max_pos: usize
+    saver     : Saver< T >                  ,
+    rt        : RiffType                    ,
+    format    : FileFormat                  ,
+    data_begin: usize                       ,
+    max_pos   : usize                       ,
+    leading   : Vec< Chunk >                ,
+    trailing  : Vec< ([u8; 4], Vec< u8 >) > ,
+    fact      : Option< usize >             ,
+    envelope  : Option< PeakEnvelope >      ,
+    peak      : Option< Peak >
 }
 
 impl< T: Write + Seek > Writer< T > {
     pub fn to(to: T, format: FileFormat) -> Option< Writer< T > > {
+        Self::start(to, format, RiffType::RIFF)
+    }
+
+    pub fn to_rf64(to: T, format: FileFormat) -> Option< Writer< T > > {
+        Self::start(to, format, RiffType::RF64)
+    }
+
+    fn start(to: T, format: FileFormat, rt: RiffType) -> Option< Writer< T > > {
+        let header    = header(format, matches!(rt, RiffType::RF64), None)?;
         let mut saver = Saver::to(to);
+        saver.save(header.as_slice())?;
+
+        let data_begin = header.len();
+        let fact       = format.sample().audio_format().requires_fact().then(|| data_begin - 12);
+        Some(Writer { saver, rt, format, data_begin, max_pos: data_begin, leading: Vec::new(), trailing: Vec::new(), fact, envelope: None, peak: None })
+    }
+
+    pub fn add_chunk(&mut self, id: &[u8; 4], data: &[u8], position: ChunkPosition) -> Option< () > {
+        if matches!(id, b"RIFF" | b"RF64" | b"ds64" | b"fmt " | b"data") || (id == b"fact" && self.fact.is_some()) {
+            return None
+        }
 
-        saver.save(b"RIFF")?;
-        saver.skip(4)?; // File size
-        saver.save(b"WAVE")?;
+        match position {
+            ChunkPosition::BeforeData => {
+                if !self.is_empty() {
+                    return None
+                }
 
-        saver.save(b"fmt ")?;
-        saver.save(&(FMT_CHUNK_SIZE as u32 - 8))?;
-        saver.save(&(format.sample().audio_format() as u16))?;
-        saver.save(& format         .num_channels())?;
-        saver.save(& format         .sample_rate ())?;
-        saver.save(& format         .byte_rate   ())?;
-        saver.save(& format         .block_align ())?;
-        saver.save(& format.sample().depth       ())?;
+                self.saver.seek(self.data_begin - 8)?;
+                self.leading.push(Chunk::new(*id, self.data_begin - 8, data.len(), position));
+                self.saver.save_chunk(id, data)?;
+                self.saver.save(b"data")?;
+                self.saver.save(&0xFFFFFFFFu32)?; // Data size
 
-        saver.save(b"data")?;
-        saver.skip(4)?; // Data size
+                self.data_begin = self.saver.pos();
+                self.max_pos    = self.data_begin;
+            },
+            ChunkPosition::AfterData => {
+                u32::try_from(data.len()).ok()?;
+                self.trailing.push((*id, data.to_vec()));
+            },
+            ChunkPosition::Data => return None
+        }
 
-        let max_pos = saver.pos();
-        Some(Writer { saver, rt: RiffType::RIFF, format, max_pos })
+        Some(())
     }
 
-    pub fn to_rf64(to: T, format: FileFormat) -> Option< Writer< T > > {
-        let mut saver = Saver::to(to);
+    fn add_metadata(&mut self, id: &[u8; 4], data: &[u8]) -> Option< () > {
+        let position = if self.is_empty() { ChunkPosition::BeforeData } else { ChunkPosition::AfterData };
+        self.add_chunk(id, data, position)
+    }
 
-        saver.save(b"RF64")?;
-        saver.skip(0xFFFFFFFF)?;
-        saver.save(b"WAVE")?;
+    pub fn add_bext(&mut self, bext: &Bext) -> Option< () > {
+        self.add_metadata(b"bext", &bext.to_bytes()?)
+    }
 
-        saver.save(b"ds64")?;
-        saver.save(&(DS64_CHUNK_SIZE as u64 - 8))?;
-        saver.skip(8)?; // File size
-        saver.skip(8)?; // Data size
-        saver.skip(8)?; // Sample count
+    pub fn add_info(&mut self, info: &Info) -> Option< () > {
+        self.add_metadata(b"LIST", &info.to_bytes())
+    }
 
-        saver.save(b"fmt ")?;
-        saver.save(&(FMT_CHUNK_SIZE as u32 - 8))?;
-        saver.save(&(format.sample().audio_format() as u16))?;
-        saver.save(& format         .num_channels())?;
-        saver.save(& format         .sample_rate ())?;
-        saver.save(& format         .byte_rate   ())?;
-        saver.save(& format         .block_align ())?;
-        saver.save(& format.sample().depth       ())?;
+    pub fn add_id3(&mut self, id3: &Id3Tag) -> Option< () > {
+        self.add_metadata(b"id3 ", &id3.to_bytes())
+    }
 
-        saver.save(b"data")?;
-        saver.skip(0xFFFFFFFF)?;
+    pub fn add_cues(&mut self, cues: &Cues) -> Option< () > {
+        let (cue, adtl) = cues.to_bytes()?;
+        self.add_metadata(b"cue ", &cue)?;
 
-        let max_pos = saver.pos();
-        Some(Writer { saver, rt: RiffType::RF64, format, max_pos })
+        match adtl {
+            Some(adtl) => self.add_metadata(b"LIST", &adtl),
+            None       => Some(())
+        }
     }
 
-    fn data_begin(&self) -> usize {
-        match self.rt {
-            RiffType::RIFF => RIFF_HEADER_SIZE                   + FMT_CHUNK_SIZE + 8,
-            RiffType::RF64 => RIFF_HEADER_SIZE + DS64_CHUNK_SIZE + FMT_CHUNK_SIZE + 8
+    pub fn add_sampler(&mut self, sampler: &Sampler) -> Option< () > {
+        self.add_metadata(b"smpl", &sampler.to_bytes()?)
+    }
+
+    pub fn add_instrument(&mut self, instrument: &Instrument) -> Option< () > {
+        self.add_metadata(b"inst", &instrument.to_bytes())
+    }
+
+    pub fn add_acid(&mut self, acid: &Acid) -> Option< () > {
+        self.add_metadata(b"acid", &acid.to_bytes()?)
+    }
+
+    pub fn add_ixml_raw(&mut self, xml: &str) -> Option< () > {
+        self.add_metadata(b"iXML", xml.as_bytes())
+    }
+
+    pub fn add_ixml(&mut self, ixml: &Ixml) -> Option< () > {
+        self.add_ixml_raw(&ixml.to_xml())
+    }
+
+    pub fn add_xmp(&mut self, xmp: &str) -> Option< () > {
+        self.add_metadata(b"_PMX", xmp.as_bytes())
+    }
+
+    pub fn add_cart(&mut self, cart: &Cart) -> Option< () > {
+        self.add_metadata(b"cart", &cart.to_bytes()?)
+    }
+
+    pub fn enable_peak_envelope(&mut self, block_size: usize) -> Option< () > {
+        if !self.is_empty() {
+            return None
+        }
+
+        self.envelope = Some(PeakEnvelope::new(self.format.num_channels() as usize, block_size)?);
+        Some(())
+    }
+
+    pub fn enable_peak(&mut self) -> Option< () > {
+        if !self.is_empty() || self.format.num_channels() == 0 {
+            return None
+        }
+
+        self.peak = Some(Peak::new(self.format.num_channels() as usize));
+        Some(())
+    }
+
+    pub fn set_start_timecode(&mut self, timecode: Timecode) -> Option< () > {
+        let time_reference = timecode.to_samples(self.format.sample_rate());
+        let offset         = Bext::time_reference_offset();
+
+        if let Some(chunk) = self.leading.iter().find(|chunk| &chunk.id() == b"bext").copied() {
+            if chunk.size() < offset + 8 {
+                return None
+            }
+
+            let restore = self.saver.pos();
+            self.saver.seek(chunk.data_offset() + offset)?;
+            self.saver.save(&time_reference)?;
+            return self.saver.seek(restore)
+        }
+
+        if let Some((_, data)) = self.trailing.iter_mut().find(|(id, _)| id == b"bext") {
+            data.get_mut(offset..offset + 8)?.copy_from_slice(&time_reference.to_le_bytes());
+            return Some(())
+        }
+
+        self.add_bext(&Bext { time_reference, ..Bext::default() })
+    }
+
+    pub fn copy_chunk< F: Read + Seek >(&mut self, from: &mut Reader< F >, chunk: &Chunk) -> Option< () > {
+        let data     = from.read_chunk(chunk)?;
+        let position = match chunk.position() {
+            ChunkPosition::BeforeData if self.is_empty() => ChunkPosition::BeforeData,
+            _                                            => ChunkPosition::AfterData
+        };
+        self.add_chunk(&chunk.id(), &data, position)
+    }
+
+    pub fn transcode< F: Read + Seek >(&mut self, from: &mut Reader< F >) -> Option< () > {
+        let num_channels = self.format.num_channels() as usize;
+
+        // Frames are copied one to one without resampling, so a rate change would leave cue, smpl and bext positions
+        // pointing at the wrong time; it is refused rather than rescaled.
+        if from.format().num_channels() as usize != num_channels || from.format().sample_rate() != self.format.sample_rate() || !self.is_empty() {
+            return None
+        }
+
+        if from.find_chunk(b"PEAK").is_some() && self.peak.is_none() {
+            self.enable_peak()?;
+        }
+
+        if let (Some(envelope), None) = (from.peak_envelope(), &self.envelope) {
+            self.enable_peak_envelope(envelope.block_size())?;
+        }
+
+        let chunks = from.chunks().to_vec();
+
+        for chunk in chunks.iter().filter(|chunk| chunk.position() == ChunkPosition::BeforeData) {
+            self.transcode_chunk(from, chunk)?;
+        }
+
+        let restore    = from.pos();
+        let mut buffer = vec![0f32; TRANSCODE_FRAMES * num_channels];
+        from.seek(0)?;
+
+        while from.pos() < from.len() {
+            let n = buffer.len().min(from.len() - from.pos());
+            from.read(&mut buffer[ ..n ])?;
+            self.write(&buffer[ ..n ])?;
+        }
+
+        from.seek(restore)?;
+
+        for chunk in chunks.iter().filter(|chunk| chunk.position() == ChunkPosition::AfterData) {
+            self.transcode_chunk(from, chunk)?;
         }
+
+        Some(())
+    }
+
+    fn transcode_chunk< F: Read + Seek >(&mut self, from: &mut Reader< F >, chunk: &Chunk) -> Option< () > {
+        match &chunk.id() {
+            b"ds64" | b"fmt " | b"data" | b"fact" => Some(()),
+            b"JUNK" | b"junk" | b"PAD " | b"FLLR" => Some(()),
+            b"levl" | b"PEAK"                     => Some(()), // Regenerated from the transcoded audio
+            _                                     => self.copy_chunk(from, chunk)
+        }
+    }
+
+    fn data_pad(&self) -> usize {
+        self.data_size() % 2
+    }
+
+    fn trailing_size(&self) -> usize {
+        self.trailing.iter().map(|(_, data)| 8 + data.len() + data.len() % 2).sum()
     }
 
     fn file_size(&self) -> usize {
-        self.max_pos
+        self.max_pos + self.data_pad() + self.trailing_size()
     }
 
     fn data_size(&self) -> usize {
-        self.max_pos - self.data_begin()
+        self.max_pos - self.data_begin
+    }
+
+    fn frames(&self) -> usize {
+        self.data_size().checked_div(self.format.block_align() as usize).unwrap_or(0)
+    }
+
+    #[cfg(feature = "async")]
+    pub(crate) fn get_mut(&mut self) -> &mut T {
+        self.saver.get_mut()
     }
 
     pub fn pos(&mut self) -> usize {
-        (self.saver.pos() - self.data_begin()) / self.format.sample().depth() as usize
+        (self.saver.pos() - self.data_begin) / self.format.sample().depth() as usize
     }
 
     pub fn len(&self) -> usize {
         self.data_size() / self.format.sample().depth() as usize
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.data_size() == 0
+    }
+
     pub fn format(&self) -> FileFormat {
         self.format
     }
@@ -104,7 +275,7 @@ impl< T: Write + Seek > Writer< T > {
     }
 
     pub fn rewind(&mut self, n: usize) -> Option< () > {
-        if self.saver.pos() - n * self.format.sample().depth() as usize >= self.data_begin() {
+        if self.saver.pos() >= self.data_begin + n * self.format.sample().depth() as usize {
             self.saver.rewind(n * self.format.sample().depth() as usize)?;
             Some(())
         }
@@ -114,87 +285,224 @@ impl< T: Write + Seek > Writer< T > {
     }
 
     pub fn seek(&mut self, n: usize) -> Option< () > {
-        self.saver.seek(self.data_begin() + n * self.format.sample().depth() as usize)?;
+        self.saver.seek(self.data_begin + n * self.format.sample().depth() as usize)?;
         self.max_pos = self.max_pos.max(self.saver.pos());
         Some(())
     }
 
     pub fn write(&mut self, from: &[f32]) -> Option< () > {
-        match self.format.sample() {
-            Sample::U8 => {
-                const A: f32 = u8::MAX as f32 / 2.0;
+        let first = (self.saver.pos() - self.data_begin) / self.format.sample().depth() as usize;
 
-                for x in from {
-                    let value = ((x + 1.0) * A) as u8;
-                    self.saver.save(&value);
-                }
-            },
-            Sample::I16 => {
-                const A: f32 = i16::MAX as f32;
-
-                for x in from {
-                    let value = (x * A) as i16;
-                    self.saver.save(&value);
-                }
-            },
-            Sample::I24 => {
-                const A: f32 = 0x7FFFFF as f32;
-
-                for x in from {
-                    let value = (x * A) as i32;
-                    let value = [
-                        (value >>  0) as u8,
-                        (value >>  8) as u8,
-                        (value >> 16) as u8
-                    ];
-                    self.saver.save(&value);
-                }
-            },
-            Sample::I32 => {
-                const A: f32 = i32::MAX as f32;
+        if let Some(envelope) = &mut self.envelope {
+            envelope.accumulate(first, from);
+        }
 
-                for x in from {
-                    let value = (x * A) as i32;
-                    self.saver.save(&value);
-                }
-            },
-            Sample::F32 => {
-                self.saver.save(from);
-            },
-            Sample::F64 => {
-                for x in from {
-                    let value = *x as f64;
-                    self.saver.save(&value);
-                }
-            }
+        if let Some(peak) = &mut self.peak {
+            peak.accumulate(first, from);
         }
 
+        let mut bytes = Vec::new();
+        encode(self.format.sample(), from, &mut bytes);
+        self.saver.save(bytes.as_slice())?;
+
         self.max_pos = self.max_pos.max(self.saver.pos());
         Some(())
     }
 
     pub fn finalize(&mut self) -> Option< () > {
+        if let Some(envelope) = &self.envelope {
+            let data = envelope.to_bytes()?;
+            self.trailing.retain(|(id, _)| id != b"levl");
+            self.trailing.push((*b"levl", data));
+        }
+
+        if let Some(peak) = &mut self.peak {
+            peak.stamp();
+            let data = peak.to_bytes()?;
+            self.trailing.retain(|(id, _)| id != b"PEAK");
+            self.trailing.push((*b"PEAK", data));
+        }
+
+        let restore = self.saver.pos();
+        self.saver.seek(self.max_pos)?;
+
+        if self.data_pad() == 1 {
+            self.saver.save(&0u8)?;
+        }
+
+        for (id, data) in &self.trailing {
+            self.saver.save_chunk(id, data)?;
+        }
+
         match self.rt {
             RiffType::RIFF => {
-                self.saver.seek(4);
-                self.saver.save(&(self.file_size() as u32))?;
-                self.saver.seek(RIFF_HEADER_SIZE + FMT_CHUNK_SIZE + 4);
+                self.saver.seek(4)?;
+                self.saver.save(&(self.file_size() as u32 - 8))?;
+                self.saver.seek(self.data_begin - 4)?;
                 self.saver.save(&(self.data_size() as u32))?
             },
             RiffType::RF64 => {
-                self.saver.seek(RIFF_HEADER_SIZE + 12);
-                self.saver.save(&(self.file_size() as u64))?;
+                self.saver.seek(RIFF_HEADER_SIZE + 8)?;
+                self.saver.save(&(self.file_size() as u64 - 8))?;
                 self.saver.save(&(self.data_size() as u64))?;
-                self.saver.save(&(self.len      () as u64))?;
+                self.saver.save(&(self.frames() as u64))?;
             }
         }
 
-        Some(())
+        if let Some(fact) = self.fact {
+            self.saver.seek(fact)?;
+            self.saver.save(&u32::try_from(self.frames()).unwrap_or(0xFFFFFFFF))?;
+        }
+
+        self.saver.seek(restore)
     }
 }
 
 impl< T: Write + Seek > Drop for Writer< T > {
+    // Errors cannot surface from here; call finalize() first to see them.
     fn drop(&mut self) {
-        self.finalize().unwrap();
+        let _ = self.finalize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use crate::{ utils::fixture::{ written, read }, common::{ Sample, FileFormat, ChunkPosition, Issue }, validator::validate };
+    use super::Writer;
+
+    fn format() -> FileFormat {
+        FileFormat::new(Sample::I16, 2, 8000)
+    }
+
+    #[test]
+    fn writes_custom_chunks() {
+        let mut file   = Cursor::new(Vec::new());
+        let mut writer = Writer::to(&mut file, format()).unwrap();
+        writer.add_chunk(b"abcd", b"lead", ChunkPosition::BeforeData).unwrap();
+        writer.add_chunk(b"efgh", b"even", ChunkPosition::BeforeData).unwrap();
+        writer.write(&[0.25, -0.25, 0.5, -0.5]).unwrap();
+        writer.add_chunk(b"wxyz", b"tail", ChunkPosition::AfterData).unwrap();
+        writer.finalize().unwrap();
+        drop(writer);
+
+        let mut reader = read(file.into_inner());
+        let ids        = reader.chunks().iter().map(|chunk| chunk.id()).collect::< Vec< _ > >();
+        assert_eq!(ids, [*b"fmt ", *b"abcd", *b"efgh", *b"data", *b"wxyz"]);
+
+        for (id, data) in [(b"abcd", &b"lead"[ .. ]), (b"efgh", b"even"), (b"wxyz", b"tail")] {
+            let chunk = reader.find_chunk(id).unwrap();
+            assert_eq!(reader.read_chunk(&chunk).unwrap(), data);
+        }
+
+        let mut samples = [0f32; 4];
+        reader.read(&mut samples).unwrap();
+        assert!(samples.iter().zip([0.25, -0.25, 0.5, -0.5]).all(|(a, b)| (a - b).abs() < 1e-4));
+    }
+
+    #[test]
+    fn rejects_misplaced_chunks() {
+        let file = written(format(), |writer| {
+            for id in [b"RIFF", b"RF64", b"ds64", b"fmt ", b"data"] {
+                assert!(writer.add_chunk(id, b"", ChunkPosition::BeforeData).is_none());
+            }
+
+            assert!(writer.add_chunk(b"abcd", b"", ChunkPosition::Data).is_none());
+            writer.write(&[0.0, 0.0]).unwrap();
+            assert!(writer.add_chunk(b"abcd", b"", ChunkPosition::BeforeData).is_none());
+        });
+
+        let reader = read(file);
+        assert_eq!(reader.chunks().len(), 2);
+        assert_eq!(reader.len(), 2);
+    }
+
+    #[test]
+    fn transcodes_with_chunks() {
+        let source = written(format(), |writer| {
+            writer.add_chunk(b"abcd", b"lead", ChunkPosition::BeforeData).unwrap();
+            writer.add_chunk(b"JUNK", &[0; 16], ChunkPosition::BeforeData).unwrap();
+            writer.write(&[0.25, -0.25, 0.5, -0.5, 0.125, 0.0]).unwrap();
+            writer.add_chunk(b"wxyz", b"tail", ChunkPosition::AfterData).unwrap();
+        });
+        let mut from = read(source);
+        let target   = written(FileFormat::new(Sample::F32, 2, 8000), |writer| writer.transcode(&mut from).unwrap());
+
+        let mut to = read(target);
+        let ids    = to.chunks().iter().map(|chunk| chunk.id()).collect::< Vec< _ > >();
+        assert_eq!(ids, [*b"fmt ", *b"fact", *b"abcd", *b"data", *b"wxyz"]);
+
+        let mut a = vec![0f32; from.len()];
+        let mut b = vec![0f32; to.len()];
+        from.read(&mut a).unwrap();
+        to.read(&mut b).unwrap();
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn regenerates_peak_chunks() {
+        let source = written(format(), |writer| {
+            writer.enable_peak().unwrap();
+            writer.enable_peak_envelope(2).unwrap();
+            writer.write(&[0.25, -0.25, 0.5, -0.5, 0.125, 0.0]).unwrap();
+        });
+        let mut from = read(source);
+        let target   = written(FileFormat::new(Sample::F32, 2, 8000), |writer| writer.transcode(&mut from).unwrap());
+
+        let mut to   = read(target);
+        let (a, b)   = (from.peak().unwrap(), to.peak().unwrap());
+        let envelope = to.peak_envelope().unwrap();
+        assert!(a.channels.iter().zip(&b.channels).all(|(a, b)| (a.value - b.value).abs() < 1e-4 && a.position == b.position));
+        assert_eq!((envelope.block_size(), envelope.len()), (2, 2));
+        assert_eq!(to.chunks().iter().filter(|chunk| matches!(&chunk.id(), b"levl" | b"PEAK")).count(), 2);
+    }
+
+    #[test]
+    fn rejects_mismatched_transcode() {
+        let mut from = read(written(format(), |writer| writer.write(&[0.0, 0.0]).unwrap()));
+
+        for format in [FileFormat::new(Sample::I16, 2, 44100), FileFormat::new(Sample::I16, 1, 8000)] {
+            written(format, |writer| assert!(writer.transcode(&mut from).is_none()));
+        }
+
+        written(format(), |writer| {
+            writer.write(&[0.0, 0.0]).unwrap();
+            assert!(writer.transcode(&mut from).is_none());
+        });
+    }
+
+    #[test]
+    fn maintains_fact() {
+        let file = written(FileFormat::new(Sample::F32, 2, 8000), |writer| {
+            writer.write(&[0.5; 6]).unwrap();
+            writer.seek(2).unwrap();
+            writer.write(&[0.25; 2]).unwrap();
+            assert!(writer.add_chunk(b"fact", &[0; 4], ChunkPosition::AfterData).is_none());
+        });
+        let mut reader = read(file);
+        let fact       = reader.find_chunk(b"fact").unwrap();
+        assert_eq!(reader.read_chunk(&fact).unwrap(), 3u32.to_le_bytes());
+        assert!(reader.warnings().is_empty());
+
+        let file   = written(format(), |writer| writer.write(&[0.0; 2]).unwrap());
+        let reader = read(file);
+        assert!(reader.find_chunk(b"fact").is_none());
+    }
+
+    #[test]
+    fn reports_bad_fact() {
+        let file       = written(FileFormat::new(Sample::F64, 1, 8000), |writer| writer.write(&[0.0; 3]).unwrap());
+        let fact       = read(file.clone()).find_chunk(b"fact").unwrap();
+        let mut broken = file.clone();
+        broken[ fact.data_offset()..fact.data_offset() + 4 ].copy_from_slice(&99u32.to_le_bytes());
+
+        let reader = read(broken.clone());
+        assert_eq!(reader.len(), 3);
+        assert!(reader.warnings().iter().any(|warning| warning.issue() == Issue::FactMismatch { stored: 99, actual: 3 }));
+        assert!(validate(Cursor::new(broken)).diagnostics().iter().any(|diagnostic| diagnostic.issue() == Issue::FactMismatch { stored: 99, actual: 3 }));
+
+        broken = file;
+        broken[ fact.offset()..fact.offset() + 4 ].copy_from_slice(b"JUNK");
+        assert!(validate(Cursor::new(broken)).diagnostics().iter().any(|diagnostic| diagnostic.issue() == Issue::MissingFact));
     }
 }